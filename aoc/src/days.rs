@@ -0,0 +1,14 @@
+// Every day's Solution in order from day 1. The given macro is expanded with the list, so the
+// runner and the example tests share it.
+macro_rules! for_each_day {
+    ($apply:ident) => {
+        $apply!(
+            day1::Day1,
+            day2::Day2,
+            day3::Day3,
+            day4::Day4,
+            day5::Day5,
+            day6::Day6
+        )
+    };
+}
@@ -0,0 +1,139 @@
+/// Runs the solution for a single day, printing the answers and how long each step took.
+/// Usage:
+///   aoc run <day> [--part 1|2] [--input path|--example]
+/// Notes:
+///   - The input defaults to the day's assets/input.txt, --example uses assets/example.txt
+///     instead.
+///   - Adding a day only needs its Solution implementation added to for_each_day in days.rs.
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Instant;
+
+use common::Solution;
+
+#[macro_use]
+mod days;
+
+const USAGE: &str = "usage: aoc run <day> [--part 1|2] [--input path|--example]";
+
+// Parses the input and solves a day's puzzle.
+type Runner = fn(&Options) -> Result<(), Box<dyn Error>>;
+
+macro_rules! runners {
+    ($($day:ty),*) => {
+        &[$(run::<$day>),*]
+    };
+}
+
+// The solution runner for each day, the first entry is day 1.
+const DAYS: &[Runner] = for_each_day!(runners);
+
+// The parsed command line arguments.
+struct Options {
+    day: usize,
+    // Only this part is run if set, otherwise both are.
+    part: Option<u8>,
+    input: PathBuf,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = DAYS[options.day - 1](&options) {
+        eprintln!("day {} failed: {}", options.day, err);
+        process::exit(1);
+    }
+}
+
+// Parse the command line arguments, without the program name.
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter().map(String::as_str);
+
+    if args.next() != Some("run") {
+        return Err("expected the run command".to_string());
+    }
+
+    let day: usize = match args.next().map(str::parse) {
+        Some(Ok(day)) if (1..=DAYS.len()).contains(&day) => day,
+        Some(_) => return Err(format!("the day must be between 1 and {}", DAYS.len())),
+        None => return Err("missing the day to run".to_string()),
+    };
+
+    let mut part = None;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--part" => match args.next() {
+                Some("1") => part = Some(1),
+                Some("2") => part = Some(2),
+                _ => return Err("--part must be 1 or 2".to_string()),
+            },
+            "--input" => match args.next() {
+                Some(path) => input = Some(PathBuf::from(path)),
+                None => return Err("--input needs a path".to_string()),
+            },
+            "--example" => input = Some(assets(day).join("example.txt")),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+
+    Ok(Options {
+        day,
+        part,
+        input: input.unwrap_or_else(|| assets(day).join("input.txt")),
+    })
+}
+
+// The assets directory of a day, found next to this crate so the runner works from any directory.
+fn assets(day: usize) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join(format!("day{}", day))
+        .join("assets")
+}
+
+// Parse the input and solve the requested parts, timing each step.
+fn run<S: Solution>(options: &Options) -> Result<(), Box<dyn Error>> {
+    let data = fs::read_to_string(&options.input)
+        .map_err(|err| format!("couldn't read {}: {}", options.input.display(), err))?;
+
+    println!("day {} ({})", options.day, options.input.display());
+
+    let start = Instant::now();
+    let input = S::parse(&data)?;
+    println!("  parse ({:?})", start.elapsed());
+
+    if options.part != Some(2) {
+        solve(1, || S::part1(&input))?;
+    }
+    if options.part != Some(1) {
+        solve(2, || S::part2(&input))?;
+    }
+
+    Ok(())
+}
+
+// Solve a single part, printing the answer and the time taken.
+fn solve(
+    part: u8,
+    solution: impl FnOnce() -> Result<String, Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+    let answer = solution()?;
+    println!("  part {}: {} ({:?})", part, answer, start.elapsed());
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::PathBuf;
+
+use common::Solution;
+
+#[macro_use]
+#[path = "../src/days.rs"]
+mod days;
+
+// The assets directory of a day.
+fn assets(day: usize) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .join(format!("day{}", day))
+        .join("assets")
+}
+
+// Solve the day's example and compare both parts to the answers recorded in example.expected,
+// which has one "part1=answer" or "part2=answer" line per part.
+fn check<S: Solution>(day: usize) {
+    let assets = assets(day);
+    let data = fs::read_to_string(assets.join("example.txt")).unwrap();
+    let expected = fs::read_to_string(assets.join("example.expected")).unwrap();
+
+    let input = S::parse(&data).unwrap();
+    let mut checked = 0;
+
+    for line in expected.lines().filter(|line| !line.trim().is_empty()) {
+        let (part, answer) = line
+            .split_once('=')
+            .unwrap_or_else(|| panic!("day {}: invalid expected line {:?}", day, line));
+
+        let actual = match part.trim() {
+            "part1" => S::part1(&input),
+            "part2" => S::part2(&input),
+            _ => panic!("day {}: unknown part {:?}", day, part),
+        };
+
+        assert_eq!(actual.unwrap(), answer.trim(), "day {} {}", day, part);
+        checked += 1;
+    }
+
+    assert_eq!(checked, 2, "day {}: expected answers for both parts", day);
+}
+
+macro_rules! checks {
+    ($($day:ty),*) => {
+        [$(check::<$day> as fn(usize)),*]
+    };
+}
+
+#[test]
+fn examples() {
+    for (index, check) in for_each_day!(checks).iter().enumerate() {
+        check(index + 1);
+    }
+}
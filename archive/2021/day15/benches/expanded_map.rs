@@ -0,0 +1,54 @@
+use std::time::Instant;
+
+use day15::map::*;
+use pathfinding::grid::*;
+use pathfinding::search::{self, Algorithm};
+
+// Generate a square risk map with pseudo random risks from 1 to 9.
+fn generate(size: usize, seed: u64) -> Vec<Vec<i32>> {
+    let mut state = seed;
+
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| {
+                    // Xorshift, good enough for benchmark input.
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state % 9) as i32 + 1
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn main() {
+    let risks = generate(100, 0x2021_0015);
+
+    // The same size as the real puzzle input, 100 x 100 tiled to 500 x 500, then a much larger
+    // tiling that would be too big to materialize comfortably.
+    for tiles in [5, 20] {
+        let map = TiledMap::new(&risks, tiles, tiles);
+        let (width, height) = map.dimensions();
+        let start = Point { x: 0, y: 0 };
+        let end = Point {
+            x: width - 1,
+            y: height - 1,
+        };
+
+        for algorithm in [Algorithm::Dijkstra, Algorithm::AStar] {
+            let timer = Instant::now();
+            let path = search::shortest(&map, start, end, Neighbors::Four, algorithm).unwrap();
+
+            println!(
+                "{:?} on {} x {} map: cost {} in {:?}",
+                algorithm,
+                width,
+                height,
+                path.cost,
+                timer.elapsed()
+            );
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod map;
+pub mod path;
+pub mod render;
@@ -1,44 +1,104 @@
-mod map;
-mod path;
+use std::env;
+use std::fs;
 
-use map::*;
-use path::*;
+use day15::map::*;
+use day15::path::*;
+use day15::render;
+use pathfinding::search::Algorithm;
 
+// Finds the lowest risk paths. Images of the full map can be written with optional arguments:
+//   --ppm <file>           PPM heatmap with the path overlaid
+//   --svg <file>           SVG heatmap with the path overlaid
+//   --frames <dir>         PPM frames of the search exploring the map
+//   --every <n>            explored positions between frames, default 5000
+//   --dijkstra             search the frames with Dijkstra instead of A*
+// Alternate routes through the unexpanded map can be listed with:
+//   --alternates <k>       the k lowest risk paths
+//   --ties                 the number of paths tied for the lowest risk, up to 1000, and the first
+//   --print-ties <n>       print up to n of the tied paths instead of only the first
 fn main() {
-    let mut map = parse("assets/input.txt");
+    let mut args = env::args().skip(1);
+    let (mut ppm, mut svg, mut frames) = (None, None, None);
+    let mut every = 5000;
+    let mut algorithm = Algorithm::AStar;
+    let mut alternates = 0;
+    let mut ties = false;
+    let mut print_ties = 1;
 
-    let mut x_max = map[0].len() - 1;
-    let mut y_max = map.len() - 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ppm" => ppm = Some(args.next().expect("missing --ppm file")),
+            "--svg" => svg = Some(args.next().expect("missing --svg file")),
+            "--frames" => frames = Some(args.next().expect("missing --frames directory")),
+            "--every" => {
+                every = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| *n > 0)
+                    .expect("--every requires a positive number")
+            }
+            "--dijkstra" => algorithm = Algorithm::Dijkstra,
+            "--alternates" => {
+                alternates = args
+                    .next()
+                    .and_then(|k| k.parse().ok())
+                    .expect("--alternates requires a number")
+            }
+            "--ties" => ties = true,
+            "--print-ties" => {
+                print_ties = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .expect("--print-ties requires a number");
+                ties = true;
+            }
+            _ => panic!("unknown argument {}", arg),
+        }
+    }
 
-    let start = Position {
-        cost: map[0][0],
-        x: 0,
-        y: 0,
-    };
+    let risks = parse_file("assets/input.txt");
 
-    let mut end = Position {
-        cost: map[y_max][x_max],
-        x: x_max,
-        y: y_max,
-    };
-
-    let path = shortest(start, end, &mut map).unwrap();
+    let map = TiledMap::new(&risks, 1, 1);
+    let (start, end) = corners(&map);
+    let path = shortest(&map, start, end).unwrap();
     pretty_print(&map, &path);
-    println!("total cost {} in {} x {} map", cost(&path), x_max, y_max);
-
-    let mut real_map = expand(map);
-    x_max = real_map[0].len() - 1;
-    y_max = real_map.len() - 1;
-    end.cost = real_map[y_max][x_max];
-    end.x = x_max;
-    end.y = y_max;
-
-    let real_path = shortest(start, end, &mut real_map).unwrap();
-    pretty_print(&real_map, &real_path);
-    println!(
-        "total cost {} in {} x {} map",
-        cost(&real_path),
-        x_max,
-        y_max
-    );
+    println!("total cost {} in {} x {} map", path.cost, end.x, end.y);
+
+    for (index, alternate) in k_shortest(&map, start, end, alternates).iter().enumerate() {
+        println!("alternate {}: total cost {}", index + 1, alternate.cost);
+    }
+
+    if ties {
+        const TIE_LIMIT: usize = 1000;
+        let tied = all_shortest(&map, start, end, TIE_LIMIT);
+        let bound = if tied.len() == TIE_LIMIT {
+            "at least "
+        } else {
+            ""
+        };
+        println!("{}{} paths tied for the lowest risk", bound, tied.len());
+
+        for tie in tied.iter().take(print_ties) {
+            pretty_print(&map, tie);
+            println!();
+        }
+    }
+
+    let real_map = TiledMap::new(&risks, 5, 5);
+    let (start, end) = corners(&real_map);
+    let real_path = shortest(&real_map, start, end).unwrap();
+    println!("total cost {} in {} x {} map", real_path.cost, end.x, end.y);
+
+    if let Some(file) = ppm {
+        fs::write(file, render::heatmap(&real_map, &real_path)).expect("failed to write ppm");
+    }
+
+    if let Some(file) = svg {
+        fs::write(file, render::svg(&real_map, &real_path)).expect("failed to write svg");
+    }
+
+    if let Some(dir) = frames {
+        render::frames(&real_map, start, end, algorithm, every, &dir)
+            .expect("failed to write frames");
+    }
 }
@@ -1,147 +1,109 @@
-use std::cmp::Ordering;
-use std::collections::HashMap;
 use std::fs::File;
-use std::hash::{Hash, Hasher};
 use std::io::{self, BufRead};
 
-use crate::path;
-
-#[derive(Clone, Copy, Debug, Eq)]
-pub struct Position {
-    pub cost: i32,
-    pub x: usize,
-    pub y: usize,
-}
-
-impl Hash for Position {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
-    }
-}
-
-impl Ord for Position {
-    // Reverse comparison to enable min-heap.
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.cost > other.cost {
-            return Ordering::Less;
-        }
-
-        if self.cost < other.cost {
-            return Ordering::Greater;
-        }
-
-        Ordering::Equal
-    }
-}
-
-impl PartialOrd for Position {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(&other))
-    }
-}
-
-impl PartialEq for Position {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
+use pathfinding::grid::*;
 
 // Parse the input file and generate the chiton risk map.
-pub fn parse(path: &str) -> Vec<Vec<i32>> {
+pub fn parse_file(path: &str) -> Vec<Vec<i32>> {
     let file = File::open(path).unwrap();
 
     io::BufReader::new(file)
         .lines()
-        .map(|line| {
-            line.unwrap()
-                .chars()
-                .collect::<Vec<char>>()
-                .iter()
-                .map(|e| e.to_string().parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
-        })
-        .collect::<Vec<Vec<i32>>>()
+        .map(|line| parse_row(&line.unwrap()))
+        .collect()
 }
 
-// Expand the map five times in each direction, increasing the values with the expansions.
-pub fn expand(map: Vec<Vec<i32>>) -> Vec<Vec<i32>> {
-    let mut new_map = Vec::<Vec<i32>>::new();
-
-    for y_add in 0..5 {
-        for y in 0..map.len() {
-            let mut row = Vec::<i32>::new();
-            for x_add in 0..5 {
-                for x in 0..map[0].len() {
-                    let mut new_value = map[y][x] + y_add + x_add;
-
-                    if new_value > 9 {
-                        new_value = new_value % 9;
-                    }
-
-                    row.push(new_value);
-                }
-            }
-            new_map.push(row);
-        }
-    }
+// Generate the chiton risk map from a string, one row per line.
+pub fn parse(data: &str) -> Vec<Vec<i32>> {
+    data.lines().map(parse_row).collect()
+}
 
-    new_map
+fn parse_row(line: &str) -> Vec<i32> {
+    line.chars()
+        .map(|e| e.to_string().parse::<i32>().unwrap())
+        .collect()
 }
 
-// Generates a list of neighbors.
-pub fn neighbors(initial: Position, map: &mut Vec<Vec<i32>>) -> Vec<Position> {
-    let mut neighbors = Vec::<Position>::new();
+// A view of the risk map repeated as tiles_x by tiles_y tiles. Each tile's risks are increased by
+// the number of tiles it is away from the top left tile, wrapping from 9 back around to 1.
+// Note: risks are calculated on demand, so the tiled map is never materialized.
+pub struct TiledMap<'a> {
+    map: &'a [Vec<i32>],
+    tiles_x: usize,
+    tiles_y: usize,
+}
 
-    if initial.x > 0 {
-        neighbors.push(Position {
-            cost: map[initial.y][initial.x - 1],
-            x: initial.x - 1,
-            y: initial.y,
-        });
+impl<'a> TiledMap<'a> {
+    pub fn new(map: &'a [Vec<i32>], tiles_x: usize, tiles_y: usize) -> Self {
+        TiledMap {
+            map,
+            tiles_x,
+            tiles_y,
+        }
     }
 
-    if initial.y > 0 {
-        neighbors.push(Position {
-            cost: map[initial.y - 1][initial.x],
-            x: initial.x,
-            y: initial.y - 1,
-        });
+    // The width and height of a single tile, which are zero for an empty map.
+    fn tile_size(&self) -> (usize, usize) {
+        (self.map.first().map_or(0, Vec::len), self.map.len())
     }
 
-    if initial.x < map[0].len() - 1 {
-        neighbors.push(Position {
-            cost: map[initial.y][initial.x + 1],
-            x: initial.x + 1,
-            y: initial.y,
-        });
-    }
+    // The risk at a position in the tiled map, or 0 if the map is empty and has no positions.
+    pub fn risk(&self, point: Point) -> i32 {
+        let (width, height) = self.tile_size();
+        if width == 0 || height == 0 {
+            return 0;
+        }
 
-    if initial.y < map.len() - 1 {
-        neighbors.push(Position {
-            cost: map[initial.y + 1][initial.x],
-            x: initial.x,
-            y: initial.y + 1,
-        });
-    }
+        let increase = (point.x / width + point.y / height) as i32;
+        let risk = self.map[point.y % height][point.x % width];
 
-    neighbors
+        (risk - 1 + increase) % 9 + 1
+    }
 }
 
-// Estimate heuristic of traversing y then x used in A* pathfinding.
-pub fn cost_estimate(start: Position, map: &Vec<Vec<i32>>) -> i32 {
-    let mut cost = start.cost;
+impl Grid for TiledMap<'_> {
+    fn dimensions(&self) -> (usize, usize) {
+        let (width, height) = self.tile_size();
 
-    let y_max = map.len() - 1;
-    let x_max = map[y_max].len() - 1;
-
-    for y in start.y..=y_max {
-        cost += map[y][start.x];
+        (width * self.tiles_x, height * self.tiles_y)
     }
 
-    for x in start.x..=x_max {
-        cost += map[y_max][x];
+    fn cost(&self, _from: Point, to: Point) -> u64 {
+        self.risk(to) as u64
     }
+}
 
-    cost
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiled_risk() {
+        let map = parse("19\n58");
+        let tiled = TiledMap::new(&map, 5, 3);
+
+        assert_eq!(tiled.dimensions(), (10, 6));
+
+        // The original tile is unchanged.
+        assert_eq!(tiled.risk(Point { x: 1, y: 1 }), 8);
+
+        // One tile to the right or down increases each risk by 1, wrapping 9 to 1.
+        assert_eq!(tiled.risk(Point { x: 2, y: 0 }), 2);
+        assert_eq!(tiled.risk(Point { x: 3, y: 0 }), 1);
+        assert_eq!(tiled.risk(Point { x: 1, y: 2 }), 1);
+
+        // Nine tiles away wraps all the way around to the original risk.
+        let map = parse("9");
+        let tiled = TiledMap::new(&map, 10, 10);
+        assert_eq!(tiled.risk(Point { x: 9, y: 0 }), 9);
+        assert_eq!(tiled.risk(Point { x: 9, y: 9 }), 9);
+        assert_eq!(tiled.risk(Point { x: 4, y: 5 }), 9);
+        assert_eq!(tiled.risk(Point { x: 4, y: 4 }), 8);
+
+        // An empty map tiles into an empty grid.
+        let map = parse("");
+        let tiled = TiledMap::new(&map, 5, 5);
+        assert_eq!(tiled.dimensions(), (0, 0));
+        assert_eq!(tiled.risk(Point { x: 0, y: 0 }), 0);
+    }
 }
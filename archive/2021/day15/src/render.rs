@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+
+use pathfinding::grid::*;
+use pathfinding::search::{self, Algorithm, Path, Step};
+
+use crate::map::TiledMap;
+
+const PATH_COLOR: [u8; 3] = [255, 255, 255];
+const FRONTIER_COLOR: [u8; 3] = [40, 220, 90];
+
+// The heatmap color of a risk, from dark blue for 1 to yellow for 9.
+fn heat(risk: i32) -> [u8; 3] {
+    let low = [20.0, 30.0, 90.0];
+    let high = [250.0, 220.0, 40.0];
+    let t = (risk.clamp(1, 9) - 1) as f64 / 8.0;
+
+    [0, 1, 2].map(|i| (low[i] + (high[i] - low[i]) * t) as u8)
+}
+
+// The color of a risk that hasn't been explored yet, a dim gray.
+fn unexplored(risk: i32) -> [u8; 3] {
+    let level = 20 + (risk.clamp(1, 9) * 6) as u8;
+    [level; 3]
+}
+
+// Encode a binary PPM image with one pixel per map position.
+fn ppm(map: &TiledMap, color: impl Fn(Point, i32) -> [u8; 3]) -> Vec<u8> {
+    let (width, height) = map.dimensions();
+    let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    image.reserve(width * height * 3);
+
+    for y in 0..height {
+        for x in 0..width {
+            let point = Point { x, y };
+            image.extend_from_slice(&color(point, map.risk(point)));
+        }
+    }
+
+    image
+}
+
+// Render the risk map as a PPM heatmap with the path overlaid.
+pub fn heatmap(map: &TiledMap, path: &Path) -> Vec<u8> {
+    let highlight: HashSet<&Point> = path.points.iter().collect();
+
+    ppm(map, |point, risk| {
+        if highlight.contains(&point) {
+            PATH_COLOR
+        } else {
+            heat(risk)
+        }
+    })
+}
+
+// Render the risk map as an SVG heatmap with the path drawn as a line through the cell centers.
+// Note: neighboring cells in a row with the same risk are merged into one rectangle to keep the
+//   file size down on large maps.
+pub fn svg(map: &TiledMap, path: &Path) -> String {
+    let (width, height) = map.dimensions();
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+        width, height
+    )
+    .unwrap();
+
+    for y in 0..height {
+        let mut x = 0;
+        while x < width {
+            let risk = map.risk(Point { x, y });
+            let mut run = 1;
+            while x + run < width && map.risk(Point { x: x + run, y }) == risk {
+                run += 1;
+            }
+
+            let [r, g, b] = heat(risk);
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                x, y, run, r, g, b
+            )
+            .unwrap();
+
+            x += run;
+        }
+    }
+
+    let points: Vec<String> = path
+        .points
+        .iter()
+        .map(|point| format!("{}.5,{}.5", point.x, point.y))
+        .collect();
+    writeln!(
+        out,
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"#ffffff\" stroke-width=\"0.4\"/>",
+        points.join(" ")
+    )
+    .unwrap();
+
+    out.push_str("</svg>\n");
+
+    out
+}
+
+// Search the map and write a PPM frame to the directory after every `every` explored positions,
+// showing the explored positions as a heatmap and the frontier in green. The final frame adds the
+// path that was found.
+pub fn frames(
+    map: &TiledMap,
+    start: Point,
+    end: Point,
+    algorithm: Algorithm,
+    every: usize,
+    dir: &str,
+) -> io::Result<Option<Path>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Unseen,
+        Open,
+        Closed,
+    }
+
+    fs::create_dir_all(dir)?;
+
+    let (width, height) = map.dimensions();
+    let mut state = vec![State::Unseen; width * height];
+    let mut explored = 0;
+    let mut frame = 0;
+    let mut result = Ok(());
+
+    let draw = |state: &[State], highlight: &HashSet<&Point>| {
+        ppm(map, |point, risk| match state[point.y * width + point.x] {
+            _ if highlight.contains(&point) => PATH_COLOR,
+            State::Unseen => unexplored(risk),
+            State::Open => FRONTIER_COLOR,
+            State::Closed => heat(risk),
+        })
+    };
+
+    let observe = |step| match step {
+        Step::Opened(point) => state[point.y * width + point.x] = State::Open,
+        Step::Closed(point) => {
+            state[point.y * width + point.x] = State::Closed;
+            explored += 1;
+
+            if explored % every == 0 && result.is_ok() {
+                let name = format!("{}/frame_{:05}.ppm", dir, frame);
+                result = fs::write(name, draw(&state, &HashSet::new()));
+                frame += 1;
+            }
+        }
+    };
+
+    let path = search::shortest_observed(map, start, end, Neighbors::Four, algorithm, observe);
+    result?;
+
+    let highlight = match &path {
+        Some(path) => path.points.iter().collect(),
+        None => HashSet::new(),
+    };
+    fs::write(
+        format!("{}/frame_{:05}.ppm", dir, frame),
+        draw(&state, &highlight),
+    )?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::parse;
+
+    fn example() -> (Vec<Vec<i32>>, Path) {
+        let risks = parse("19\n11");
+        let path = Path {
+            points: vec![
+                Point { x: 0, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 1, y: 1 },
+            ],
+            cost: 2,
+        };
+
+        (risks, path)
+    }
+
+    #[test]
+    fn heatmap_test() {
+        let (risks, path) = example();
+        let image = heatmap(&TiledMap::new(&risks, 1, 1), &path);
+
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 2 * 2 * 3);
+
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels[0..3], PATH_COLOR);
+        assert_eq!(pixels[3..6], heat(9));
+        assert_eq!(pixels[6..9], PATH_COLOR);
+    }
+
+    #[test]
+    fn svg_test() {
+        let (risks, path) = example();
+        let image = svg(&TiledMap::new(&risks, 1, 1), &path);
+
+        assert!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 2 2\""));
+        // The bottom row has the same risk, so it is a single rectangle.
+        assert!(image.contains("<rect x=\"0\" y=\"1\" width=\"2\" height=\"1\" fill=\"#141e5a\"/>"));
+        assert!(image.contains("points=\"0.5,0.5 0.5,1.5 1.5,1.5\""));
+        assert!(image.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn frames_test() {
+        let (risks, path) = example();
+        let map = TiledMap::new(&risks, 1, 1);
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 1, y: 1 };
+        let dir = std::env::temp_dir().join(format!("day15_frames_{}", std::process::id()));
+        let dir = dir.to_str().unwrap();
+
+        let found = frames(&map, start, end, Algorithm::Dijkstra, 1, dir).unwrap();
+        assert_eq!(found, Some(path));
+
+        // A frame for each of the three closed points, then the final frame with the path.
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "frame_00000.ppm",
+                "frame_00001.ppm",
+                "frame_00002.ppm",
+                "frame_00003.ppm"
+            ]
+        );
+
+        let pixels = |frame: usize| {
+            let image = fs::read(format!("{}/{}", dir, names[frame])).unwrap();
+            image[image.len() - 2 * 2 * 3..].to_vec()
+        };
+        assert_eq!(
+            pixels(0),
+            [heat(1), unexplored(9), unexplored(1), unexplored(1)].concat()
+        );
+        assert_eq!(
+            pixels(1),
+            [heat(1), FRONTIER_COLOR, heat(1), unexplored(1)].concat()
+        );
+        assert_eq!(
+            pixels(2),
+            [heat(1), FRONTIER_COLOR, heat(1), heat(1)].concat()
+        );
+        assert_eq!(
+            pixels(3),
+            [PATH_COLOR, FRONTIER_COLOR, PATH_COLOR, PATH_COLOR].concat()
+        );
+
+        // Only every second closed point is drawn, plus the final frame.
+        fs::remove_dir_all(dir).unwrap();
+        frames(&map, start, end, Algorithm::Dijkstra, 2, dir).unwrap();
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
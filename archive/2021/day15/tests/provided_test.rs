@@ -0,0 +1,46 @@
+use day15::map::*;
+use day15::path::*;
+
+const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+#[test]
+fn provided_tests() {
+    let risks = parse(EXAMPLE);
+
+    let map = TiledMap::new(&risks, 1, 1);
+    let (start, end) = corners(&map);
+    assert_eq!(shortest(&map, start, end).unwrap().cost, 40);
+
+    let map = TiledMap::new(&risks, 5, 5);
+    let (start, end) = corners(&map);
+    assert_eq!(shortest(&map, start, end).unwrap().cost, 315);
+}
+
+#[test]
+fn alternate_paths() {
+    let risks = parse(EXAMPLE);
+    let map = TiledMap::new(&risks, 1, 1);
+    let (start, end) = corners(&map);
+
+    let paths = k_shortest(&map, start, end, 5);
+    assert_eq!(paths.len(), 5);
+    assert_eq!(paths[0].cost, 40);
+    assert!(paths.windows(2).all(|pair| pair[0].cost <= pair[1].cost));
+
+    let ties = all_shortest(&map, start, end, 100);
+    assert!(!ties.is_empty());
+    assert!(ties.iter().all(|path| path.cost == 40));
+    assert_eq!(
+        paths.iter().take_while(|path| path.cost == 40).count(),
+        ties.len().min(5)
+    );
+}
@@ -0,0 +1,94 @@
+use std::time::Instant;
+
+use day16::calculator::*;
+use day16::parser::*;
+
+// Accumulates bits and encodes them as a hexadecimal transmission.
+struct BitWriter {
+    bits: Vec<u8>,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, count: usize) {
+        for shift in (0..count).rev() {
+            self.bits.push(((value >> shift) & 1) as u8);
+        }
+    }
+
+    // Header for an operator packet counting its sub packets.
+    fn operator(&mut self, class: u64, count: u64) {
+        self.write(1, 3);
+        self.write(class, 3);
+        self.write(1, 1);
+        self.write(count, 11);
+    }
+
+    // A literal packet small enough to fit in a single group.
+    fn literal(&mut self, value: u64) {
+        self.write(1, 3);
+        self.write(4, 3);
+        self.write(value & 0b1111, 5);
+    }
+
+    fn hex(mut self) -> String {
+        while !self.bits.len().is_multiple_of(4) {
+            self.bits.push(0);
+        }
+
+        self.bits
+            .chunks(4)
+            .map(|nibble| {
+                let digit = nibble.iter().fold(0, |acc, bit| (acc << 1) | bit);
+                char::from_digit(digit as u32, 16)
+                    .unwrap()
+                    .to_ascii_uppercase()
+            })
+            .collect()
+    }
+}
+
+// Generate a sum of chains, each chain being depth nested sums of a literal and the next link.
+fn generate(chains: u64, depth: u64) -> String {
+    let mut writer = BitWriter { bits: vec![] };
+
+    writer.operator(0, chains);
+    for _ in 0..chains {
+        for link in 0..depth {
+            writer.operator(0, 2);
+            writer.literal(link);
+        }
+        writer.literal(0);
+    }
+
+    writer.hex()
+}
+
+fn bench(chains: u64, depth: u64) {
+    let transmission = generate(chains, depth);
+
+    let start = Instant::now();
+    let packet = parse(&transmission).expect("generated packet is truncated");
+    let parsed = start.elapsed();
+    let total = calculate(&packet).unwrap();
+    let calculated = start.elapsed() - parsed;
+
+    let megabytes = transmission.len() as f64 / 1_000_000.0;
+    println!(
+        "{} chains of depth {}: {:.2} MB parsed in {:?} ({:.1} MB/s), calculated {} in {:?}",
+        chains,
+        depth,
+        megabytes,
+        parsed,
+        megabytes / parsed.as_secs_f64(),
+        total,
+        calculated,
+    );
+}
+
+fn main() {
+    // Doubling the input size should double the parse time.
+    bench(100, 1000);
+    bench(200, 1000);
+    bench(400, 1000);
+    bench(1, 10000);
+}
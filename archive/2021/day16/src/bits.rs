@@ -0,0 +1,111 @@
+// A streaming reader over the bits of a hexadecimal transmission. The hex digits are decoded as
+// they are read, so no intermediate bit array is ever built.
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    // Create a reader positioned at the first bit of the transmission.
+    pub fn new(data: &'a str) -> Self {
+        BitReader {
+            data: data.trim_end().as_bytes(),
+            position: 0,
+        }
+    }
+
+    // The number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    // The number of bits left in the transmission.
+    pub fn remaining(&self) -> usize {
+        self.data.len() * 4 - self.position
+    }
+
+    // Read the next count bits as a big endian number, or None if the transmission is too short
+    // or the bits come from a character that isn't a hexadecimal digit.
+    // Note: at most 64 bits can be read at once.
+    pub fn read_bits(&mut self, count: usize) -> Option<u64> {
+        if count > 64 {
+            panic!("cannot read {} bits at once", count);
+        }
+
+        if count > self.remaining() {
+            return None;
+        }
+
+        let mut value = 0u64;
+        let mut needed = count;
+
+        // Take as many bits as possible from each hex digit instead of one bit at a time.
+        while needed > 0 {
+            let offset = self.position % 4;
+            let available = 4 - offset;
+            let take = available.min(needed);
+
+            let digit = nibble(self.data[self.position / 4])?;
+            let bits = (digit >> (available - take)) & ((1 << take) - 1);
+
+            value = (value << take) | bits as u64;
+            self.position += take;
+            needed -= take;
+        }
+
+        Some(value)
+    }
+}
+
+// Decode a single hexadecimal digit, or None if it isn't one.
+fn nibble(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_across_digits() {
+        let mut reader = BitReader::new("D2FE28");
+
+        assert_eq!(reader.remaining(), 24);
+        assert_eq!(reader.read_bits(3), Some(6));
+        assert_eq!(reader.read_bits(3), Some(4));
+        assert_eq!(reader.read_bits(5), Some(0b10111));
+        assert_eq!(reader.read_bits(5), Some(0b11110));
+        assert_eq!(reader.read_bits(5), Some(0b00101));
+        assert_eq!(reader.position(), 21);
+        assert_eq!(reader.read_bits(4), None);
+        assert_eq!(reader.read_bits(3), Some(0));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn read_wide_values() {
+        let mut reader = BitReader::new("ffffffffffffffff1");
+
+        assert_eq!(reader.read_bits(64), Some(u64::MAX));
+        assert_eq!(reader.read_bits(4), Some(1));
+        assert_eq!(reader.read_bits(0), Some(0));
+    }
+
+    #[test]
+    fn read_invalid_digits() {
+        let mut reader = BitReader::new("A\nG");
+
+        assert_eq!(reader.read_bits(4), Some(10));
+        assert_eq!(reader.read_bits(1), None);
+
+        // Only the digits that are read are decoded.
+        let mut reader = BitReader::new("1z");
+        assert_eq!(reader.read_bits(4), Some(1));
+        assert_eq!(reader.read_bits(8), None);
+    }
+}
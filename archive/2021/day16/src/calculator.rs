@@ -1,7 +1,7 @@
-use crate::parser::Token;
+use crate::parser::Packet;
 
-// Calculate the value of the tokens based on the following operations.
-// Token value -> operation
+// Calculate the value of the packet based on the following operations.
+// Packet type ID -> operation
 //  0 -> sum of subvalues
 //  1 -> product of subvalues
 //  2 -> minimum of subvalues
@@ -10,115 +10,64 @@ use crate::parser::Token;
 //  5 -> first value > second value
 //  6 -> first value < second value
 //  7 -> first value = second value
-pub fn calculate(tokens: &mut Vec<Token>) -> Option<i64> {
-    let token = tokens.pop()?;
-    match token.class {
-        // Sum of values.
-        0 => {
-            let mut values = Vec::<i64>::new();
+// Note: None is returned for unknown type IDs and comparisons without exactly two sub packets.
+pub fn calculate(packet: &Packet) -> Option<i64> {
+    if packet.class == 4 {
+        return Some(packet.value);
+    }
 
-            while tokens.len() > 0 {
-                values.push(calculate(tokens).unwrap());
-            }
-            let result: i64 = values.iter().sum();
+    let values = packet
+        .children
+        .iter()
+        .map(calculate)
+        .collect::<Option<Vec<i64>>>()?;
 
-            println!("add {:?}", values);
-            println!("result {}", result);
+    operate(packet.class, &values)
+}
 
-            Some(result)
-        }
+// Apply an operator packet type ID to the already calculated values of its sub packets.
+pub fn operate(class: i64, values: &[i64]) -> Option<i64> {
+    match class {
+        // Sum of values.
+        0 => values.iter().copied().reduce(|acc, x| acc + x),
 
         // Product of values.
-        1 => {
-            let mut values = Vec::<i64>::new();
-
-            while tokens.len() > 0 {
-                values.push(calculate(tokens).unwrap());
-            }
-            let result: i64 = values.iter().fold(1, |acc, x| acc * x);
-
-            println!("mult {:?}", values);
-            println!("result {}", result);
-
-            Some(result)
-        }
+        1 => values.iter().copied().reduce(|acc, x| acc * x),
 
         // Minimum of values.
-        2 => {
-            let mut values = Vec::<i64>::new();
-
-            while tokens.len() > 0 {
-                values.push(calculate(tokens).unwrap());
-            }
-            values.sort();
-            let result = *values.get(0).unwrap();
-
-            println!("min {:?}", values);
-            println!("result {}", result);
-
-            Some(result)
-        }
+        2 => values.iter().copied().min(),
 
         // Maximum of values.
-        3 => {
-            let mut values = Vec::<i64>::new();
-
-            while tokens.len() > 0 {
-                values.push(calculate(tokens).unwrap());
-            }
-            values.sort();
-            let result = *values.get(values.len() - 1).unwrap();
-
-            println!("max {:?}", values);
-            println!("result {}", result);
-
-            Some(result)
-        }
-
-        4 => {
-            println!("val {}", token.value);
-            Some(token.value)
-        }
+        3 => values.iter().copied().max(),
 
         // First value greater than second.
         5 => {
-            let first = calculate(tokens).unwrap();
-            let second = calculate(tokens).unwrap();
-
-            let result = if first > second { 1 } else { 0 };
-
-            println!("{} > {}", first, second);
-            println!("result {}", result);
-
-            Some(result)
+            let (first, second) = pair(values)?;
+            Some(if first > second { 1 } else { 0 })
         }
 
         // First value less than second.
         6 => {
-            let first = calculate(tokens).unwrap();
-            println!("first {}", first);
-            let second = calculate(tokens).unwrap();
-            let result = if first < second { 1 } else { 0 };
-
-            println!("{} < {}", first, second);
-            println!("result {}", result);
-
-            Some(result)
+            let (first, second) = pair(values)?;
+            Some(if first < second { 1 } else { 0 })
         }
 
         // First value equal to second.
         7 => {
-            let first = calculate(tokens).unwrap();
-            let second = calculate(tokens).unwrap();
-            let result = if first == second { 1 } else { 0 };
-
-            println!("{} == {}", first, second);
-            println!("result {}", result);
-
-            Some(result)
+            let (first, second) = pair(values)?;
+            Some(if first == second { 1 } else { 0 })
         }
 
-        _ => panic!("invalid type ID {}", token.class),
+        // Unknown type IDs have no value.
+        _ => None,
+    }
+}
+
+// The two values of a comparison.
+fn pair(values: &[i64]) -> Option<(i64, i64)> {
+    match values {
+        [first, second] => Some((*first, *second)),
+        _ => None,
     }
 }
 
@@ -126,178 +75,137 @@ pub fn calculate(tokens: &mut Vec<Token>) -> Option<i64> {
 mod tests {
     use super::*;
 
-    fn token(class: i64, value: i64) -> Token {
-        Token {
+    fn val(value: i64) -> Packet {
+        Packet {
+            version: 0,
+            class: 4,
+            value,
+            children: vec![],
+            bits: 0..0,
+        }
+    }
+
+    fn op(class: i64, children: Vec<Packet>) -> Packet {
+        Packet {
             version: 0,
-            class: class,
-            value: value,
+            class,
+            value: 0,
+            children,
+            bits: 0..0,
         }
     }
 
     #[test]
     fn basic_functions() {
         // + 3 = 3
-        let mut chain = vec![token(0, 0), token(4, 3)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 3);
+        assert_eq!(calculate(&op(0, vec![val(3)])).unwrap(), 3);
 
         // + 1 3 = 4
-        let mut chain = vec![token(0, 0), token(4, 1), token(4, 3)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 4);
+        assert_eq!(calculate(&op(0, vec![val(1), val(3)])).unwrap(), 4);
 
         // * 2 = 2
-        let mut chain = vec![token(1, 0), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 2);
+        assert_eq!(calculate(&op(1, vec![val(2)])).unwrap(), 2);
 
         // * 1 3 = 3
-        let mut chain = vec![token(1, 0), token(4, 1), token(4, 3)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 3);
+        assert_eq!(calculate(&op(1, vec![val(1), val(3)])).unwrap(), 3);
 
         // min 1 2 3 = 1
-        let mut chain = vec![token(2, 0), token(4, 1), token(4, 2), token(4, 3)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 1);
+        assert_eq!(calculate(&op(2, vec![val(1), val(2), val(3)])).unwrap(), 1);
 
         // min 2 = 2
-        let mut chain = vec![token(1, 0), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 2);
+        assert_eq!(calculate(&op(2, vec![val(2)])).unwrap(), 2);
 
         // max 1 2 3 = 3
-        let mut chain = vec![token(3, 0), token(4, 1), token(4, 2), token(4, 3)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 3);
+        assert_eq!(calculate(&op(3, vec![val(1), val(2), val(3)])).unwrap(), 3);
 
         // max 2 = 2
-        let mut chain = vec![token(3, 0), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 2);
+        assert_eq!(calculate(&op(3, vec![val(2)])).unwrap(), 2);
 
         // val 6 = 6
-        let mut chain = vec![token(4, 6)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 6);
+        assert_eq!(calculate(&val(6)).unwrap(), 6);
 
         // > 1 2 = 0
-        let mut chain = vec![token(5, 0), token(4, 1), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 0);
+        assert_eq!(calculate(&op(5, vec![val(1), val(2)])).unwrap(), 0);
 
         // > 2 1 = 1
-        let mut chain = vec![token(5, 0), token(4, 2), token(4, 1)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 1);
+        assert_eq!(calculate(&op(5, vec![val(2), val(1)])).unwrap(), 1);
 
         // < 1 2 = 1
-        let mut chain = vec![token(6, 0), token(4, 1), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 1);
+        assert_eq!(calculate(&op(6, vec![val(1), val(2)])).unwrap(), 1);
 
         // < 2 1 = 0
-        let mut chain = vec![token(6, 0), token(4, 2), token(4, 1)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 0);
+        assert_eq!(calculate(&op(6, vec![val(2), val(1)])).unwrap(), 0);
 
         // = 1 2 = 0
-        let mut chain = vec![token(7, 0), token(4, 1), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 0);
+        assert_eq!(calculate(&op(7, vec![val(1), val(2)])).unwrap(), 0);
 
         // = 2 2 = 1
-        let mut chain = vec![token(7, 0), token(4, 2), token(4, 2)];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 1);
+        assert_eq!(calculate(&op(7, vec![val(2), val(2)])).unwrap(), 1);
     }
 
     #[test]
     fn basic_chain() {
         // * + 1 2 3 = 9
-        let mut chain = vec![
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 3),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 9);
+        let chain = op(1, vec![op(0, vec![val(1), val(2)]), val(3)]);
+        assert_eq!(calculate(&chain).unwrap(), 9);
 
         // > * + 1 2 3 1 = 1
-        let mut chain = vec![
-            token(5, 0),
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 3),
-            token(4, 1),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 1);
+        let chain = op(
+            5,
+            vec![op(1, vec![op(0, vec![val(1), val(2)]), val(3)]), val(1)],
+        );
+        assert_eq!(calculate(&chain).unwrap(), 1);
 
         // < * + 1 2 2 1 = 0
-        let mut chain = vec![
-            token(6, 0),
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 2),
-            token(4, 1),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 0);
+        let chain = op(
+            6,
+            vec![op(1, vec![op(0, vec![val(1), val(2)]), val(2)]), val(1)],
+        );
+        assert_eq!(calculate(&chain).unwrap(), 0);
 
         // max * + 1 2 2 7 8 = 8
-        let mut chain = vec![
-            token(3, 0),
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 2),
-            token(4, 7),
-            token(4, 8),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 8);
+        let chain = op(
+            3,
+            vec![
+                op(1, vec![op(0, vec![val(1), val(2)]), val(2)]),
+                val(7),
+                val(8),
+            ],
+        );
+        assert_eq!(calculate(&chain).unwrap(), 8);
 
         // min * + 1 2 2 7 8 = 6
-        let mut chain = vec![
-            token(2, 0),
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 2),
-            token(4, 7),
-            token(4, 8),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 6);
+        let chain = op(
+            2,
+            vec![
+                op(1, vec![op(0, vec![val(1), val(2)]), val(2)]),
+                val(7),
+                val(8),
+            ],
+        );
+        assert_eq!(calculate(&chain).unwrap(), 6);
     }
 
     #[test]
     fn nested_chains() {
         // max * + 1 2 2 5 * > 2 1 8  = 8
-        let mut chain = vec![
-            token(3, 0),
-            token(1, 0),
-            token(0, 0),
-            token(4, 1),
-            token(4, 2),
-            token(4, 2),
-            token(4, 5),
-            token(1, 0),
-            token(5, 0),
-            token(4, 2),
-            token(4, 1),
-            token(4, 8),
-        ];
-        chain.reverse();
-        assert_eq!(calculate(&mut chain).unwrap(), 8);
+        let chain = op(
+            3,
+            vec![
+                op(1, vec![op(0, vec![val(1), val(2)]), val(2)]),
+                val(5),
+                op(1, vec![op(5, vec![val(2), val(1)]), val(8)]),
+            ],
+        );
+        assert_eq!(calculate(&chain).unwrap(), 8);
+    }
+
+    #[test]
+    fn malformed_comparison() {
+        // > 1 = ?
+        assert_eq!(calculate(&op(5, vec![val(1)])), None);
+
+        // + 1 (type 9 2) = ?
+        assert_eq!(calculate(&op(0, vec![val(1), op(9, vec![val(2)])])), None);
     }
 }
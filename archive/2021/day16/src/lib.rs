@@ -1,2 +1,5 @@
+pub mod bits;
 pub mod calculator;
 pub mod parser;
+pub mod render;
+pub mod stats;
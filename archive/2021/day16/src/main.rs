@@ -1,14 +1,47 @@
+use std::env;
+
 use day16::calculator::*;
 use day16::parser::*;
+use day16::render::*;
+use day16::stats::*;
 
+// Decodes the transmission, the output format can be chosen with an optional argument:
+//   --tree  indented tree of every packet
+//   --dot   Graphviz DOT graph only, for piping into `dot`
+//   --json  statistics and the message total as a JSON object only
 fn main() {
-    let mut tokens = parse_file("assets/input.txt");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let packet = parse_file("assets/input.txt").expect("truncated or invalid transmission");
+    let stats = stats(&packet);
 
-    let version_total: i64 = tokens.iter().map(|token| token.version).sum();
-    println!("version total {}", version_total);
+    match args.first().map(String::as_str) {
+        None => {
+            println!("version total {}", stats.version_total);
+            println!("message: {}", sexpr(&packet));
+        }
+        Some("--tree") => print!("{}", tree(&packet)),
+        Some("--dot") => {
+            print!("{}", dot(&packet));
+            return;
+        }
+        Some("--json") => {
+            println!("{}", json(&stats, calculate(&packet)));
+            return;
+        }
+        Some(arg) => panic!("unknown argument {}, expected --tree, --dot or --json", arg),
+    }
 
-    pretty_print(&tokens);
+    println!(
+        "{} packets, {} literals, depth {}, {} bits",
+        stats.packets(),
+        stats.literals,
+        stats.depth,
+        stats.bits
+    );
+    for (class, count) in &stats.types {
+        println!("  {:>4} {}", symbol(*class), count);
+    }
 
-    let total = calculate(&mut tokens).unwrap();
+    let total = calculate(&packet).expect("invalid transmission");
     println!("message total {}", total);
 }
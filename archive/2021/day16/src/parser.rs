@@ -1,15 +1,23 @@
 use std::fs::File;
 use std::io::{self, BufRead};
+use std::ops::Range;
 
-#[derive(Debug)]
-pub struct Token {
+use crate::bits::BitReader;
+
+// A decoded packet, operator packets hold their sub packets as children.
+// Note: bits is the range of the transmission the packet was decoded from, including its sub
+//   packets.
+#[derive(Debug, PartialEq)]
+pub struct Packet {
     pub version: i64,
     pub class: i64,
     pub value: i64,
+    pub children: Vec<Packet>,
+    pub bits: Range<usize>,
 }
 
-// Generate packets from the input file.
-pub fn parse_file(path: &str) -> Vec<Token> {
+// Generate the outermost packet from the input file.
+pub fn parse_file(path: &str) -> Option<Packet> {
     let file = File::open(path).unwrap();
 
     let lines: Vec<String> = io::BufReader::new(file)
@@ -24,144 +32,166 @@ pub fn parse_file(path: &str) -> Vec<Token> {
     parse(&lines[0])
 }
 
-// Generate packets from a transmission string.
-pub fn parse(data: &String) -> Vec<Token> {
-    parse_packets(&mut convert(data))
+// Generate the outermost packet from a transmission string.
+pub fn parse(data: &str) -> Option<Packet> {
+    parse_packet(&mut BitReader::new(data))
 }
 
-// Convert from a hexadecimal string to a bit array.
-fn convert(data: &String) -> Vec<u8> {
-    let mut packets = Vec::<u8>::new();
-
-    for num in data.chars() {
-        let packet = match num {
-            '0' => [0, 0, 0, 0],
-            '1' => [0, 0, 0, 1],
-            '2' => [0, 0, 1, 0],
-            '3' => [0, 0, 1, 1],
-            '4' => [0, 1, 0, 0],
-            '5' => [0, 1, 0, 1],
-            '6' => [0, 1, 1, 0],
-            '7' => [0, 1, 1, 1],
-            '8' => [1, 0, 0, 0],
-            '9' => [1, 0, 0, 1],
-            'A' => [1, 0, 1, 0],
-            'B' => [1, 0, 1, 1],
-            'C' => [1, 1, 0, 0],
-            'D' => [1, 1, 0, 1],
-            'E' => [1, 1, 1, 0],
-            'F' => [1, 1, 1, 1],
-            _ => panic!("invalid bits found {}", num),
-        };
-
-        packets.append(&mut packet.to_vec());
-    }
+// Calculates a literal value, stored as groups of four bits each prefixed with a continue bit.
+// Values that don't fit in an i64 give None rather than losing their high bits.
+fn value(reader: &mut BitReader) -> Option<i64> {
+    let mut value: i64 = 0;
 
-    packets.reverse();
+    loop {
+        let group = reader.read_bits(5)?;
+        value = value.checked_mul(16)? | (group & 0b1111) as i64;
 
-    packets
-}
+        if group & 0b10000 == 0 {
+            break;
+        }
+    }
 
-// Calculates the packet header value.
-fn header(packets: &mut Vec<u8>) -> Option<i64> {
-    decimal_value(packets, 3)
+    Some(value)
 }
 
-// Calculate the number of sub packets.
-fn length(packets: &mut Vec<u8>) -> Option<i64> {
-    if packets.pop()? == 0 {
-        decimal_value(packets, 15)
-    } else {
-        decimal_value(packets, 11)
-    }
-}
+// Generate the sub packets of an operator packet.
+fn children(reader: &mut BitReader) -> Option<Vec<Packet>> {
+    let mut children = Vec::<Packet>::new();
 
-// Calculates a packet value and whether it is the last one.
-fn value(packets: &mut Vec<u8>) -> Option<i64> {
-    let mut end = false;
-    let mut value = Vec::<u8>::new();
+    if reader.read_bits(1)? == 0 {
+        // The length is the total number of bits used by the sub packets.
+        let length = reader.read_bits(15)? as usize;
+        let end = reader.position() + length;
 
-    while !end {
-        end = packets.pop()? == 0;
-        for _ in 0..4 {
-            value.push(packets.pop()?);
+        while reader.position() < end {
+            children.push(parse_packet(reader)?);
         }
-    }
-
-    let length = value.len();
-    value.reverse();
-
-    decimal_value(&mut value, length)
-}
-
-// Calculate the decimal value of a binary array.
-fn decimal_value(binary: &mut Vec<u8>, len: usize) -> Option<i64> {
-    let mut value = 0;
+    } else {
+        // The length is the number of sub packets.
+        let count = reader.read_bits(11)?;
 
-    for index in 1..=len {
-        let digit = match binary.pop() {
-            Some(val) => val,
-            None => return None,
-        };
-        value += 2i64.pow((len - index) as u32) * (digit as i64);
+        for _ in 0..count {
+            children.push(parse_packet(reader)?);
+        }
     }
 
-    Some(value)
+    Some(children)
 }
 
-// Generate a token from the available packets.
-fn parse_packet(packets: &mut Vec<u8>) -> Option<Token> {
-    let version = header(packets)?;
-    let class = header(packets)?;
-
-    if class == 4 {
-        let val = value(packets)?;
+// Generate a packet, and any sub packets, from the reader.
+fn parse_packet(reader: &mut BitReader) -> Option<Packet> {
+    let start = reader.position();
+    let version = reader.read_bits(3)? as i64;
+    let class = reader.read_bits(3)? as i64;
 
-        Some(Token {
-            version: version,
-            class: class,
-            value: val,
-        })
+    let (value, children) = if class == 4 {
+        (value(reader)?, vec![])
     } else {
-        let length = length(packets)?;
+        (0, children(reader)?)
+    };
+
+    Some(Packet {
+        version,
+        class,
+        value,
+        children,
+        bits: start..reader.position(),
+    })
+}
 
-        Some(Token {
-            version: version,
-            class: class,
-            value: length,
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_packet() {
+        assert_eq!(
+            parse("D2FE28"),
+            Some(Packet {
+                version: 6,
+                class: 4,
+                value: 2021,
+                children: vec![],
+                bits: 0..21,
+            })
+        );
+    }
+
+    #[test]
+    fn operator_packets() {
+        // Length type 0, sub packets 10 and 20.
+        let packet = parse("38006F45291200").unwrap();
+        assert_eq!(packet.class, 6);
+        assert_eq!(
+            packet
+                .children
+                .iter()
+                .map(|c| c.value)
+                .collect::<Vec<i64>>(),
+            vec![10, 20]
+        );
+        assert_eq!(packet.bits, 0..49);
+        assert_eq!(packet.children[0].bits, 22..33);
+        assert_eq!(packet.children[1].bits, 33..49);
+
+        // Length type 1, sub packets 1, 2 and 3.
+        let packet = parse("EE00D40C823060").unwrap();
+        assert_eq!(packet.class, 3);
+        assert_eq!(
+            packet
+                .children
+                .iter()
+                .map(|c| c.value)
+                .collect::<Vec<i64>>(),
+            vec![1, 2, 3]
+        );
     }
-}
 
-// Generate all tokens from the packets.
-fn parse_packets(packets: &mut Vec<u8>) -> Vec<Token> {
-    let mut tokens = Vec::<Token>::new();
+    // The transmission of a literal packet made from the given four bit groups.
+    fn literal(groups: &[u8]) -> String {
+        let mut bits = String::from("000100");
+        for (index, group) in groups.iter().enumerate() {
+            let more = index + 1 < groups.len();
+            bits.push_str(&format!("{}{:04b}", more as u8, group));
+        }
+        while bits.len() % 4 != 0 {
+            bits.push('0');
+        }
 
-    while let Some(token) = parse_packet(packets) {
-        tokens.push(token);
+        bits.as_bytes()
+            .chunks(4)
+            .map(|digit| {
+                let digit = u32::from_str_radix(std::str::from_utf8(digit).unwrap(), 2).unwrap();
+                char::from_digit(digit, 16).unwrap()
+            })
+            .collect()
     }
 
-    // Reverse the tokens for easier processing.
-    tokens.reverse();
+    #[test]
+    fn wide_literals() {
+        let mut groups = vec![0b0111];
+        groups.extend([0b1111; 15]);
+        assert_eq!(parse(&literal(&groups)).unwrap().value, i64::MAX);
+
+        // One more bit than an i64 holds, and more groups than fit in 64 bits.
+        groups[0] = 0b1000;
+        assert_eq!(parse(&literal(&groups)), None);
+        assert_eq!(parse(&literal(&[0b0001; 17])), None);
+
+        // Leading zero groups don't count toward the width.
+        let mut groups = vec![0; 4];
+        groups.extend([0b0111, 0b1111]);
+        assert_eq!(parse(&literal(&groups)).unwrap().value, 0x7f);
+    }
 
-    tokens
-}
+    #[test]
+    fn truncated_packet() {
+        assert_eq!(parse("38006F452912"), None);
+    }
 
-// Pretty prints the tokens for visualization.
-pub fn pretty_print(tokens: &[Token]) {
-    println!("message:");
-    for index in (0..tokens.len()).rev() {
-        match tokens[index].class {
-            0 => print!("+ "),
-            1 => print!("* "),
-            2 => print!("min "),
-            3 => print!("max "),
-            4 => print!("{} ", tokens[index].value),
-            5 => print!("> "),
-            6 => print!("< "),
-            7 => print!("== "),
-            _ => print!("!!! {} ", tokens[index].class),
-        }
+    #[test]
+    fn invalid_packet() {
+        assert_eq!(parse("D2FE2G"), None);
+        assert_eq!(parse("38006F4529\n1200"), None);
     }
-    println!();
 }
@@ -0,0 +1,201 @@
+use std::fmt::Write;
+
+use crate::calculator::operate;
+use crate::parser::Packet;
+
+// The operator symbol used when rendering a packet type ID.
+pub fn symbol(class: i64) -> String {
+    match class {
+        0 => "+".to_string(),
+        1 => "*".to_string(),
+        2 => "min".to_string(),
+        3 => "max".to_string(),
+        4 => "val".to_string(),
+        5 => ">".to_string(),
+        6 => "<".to_string(),
+        7 => "==".to_string(),
+        _ => format!("type{}", class),
+    }
+}
+
+// Render the packets as an indented tree, one packet per line with its calculated value, version,
+// type ID and bit range.
+// Example:
+//   + = 3 (version 6, type 0, bits 0..40)
+//     1 (version 6, type 4, bits 18..29)
+//     2 (version 2, type 4, bits 29..40)
+pub fn tree(packet: &Packet) -> String {
+    let mut values = vec![];
+    calculate_all(packet, &mut values);
+
+    let mut out = String::new();
+    write_tree(packet, 0, &mut values.into_iter(), &mut out);
+
+    out
+}
+
+// Render the packets as an S-expression, e.g. "(max (* (+ 1 2) 2) 5)".
+pub fn sexpr(packet: &Packet) -> String {
+    let mut out = String::new();
+    write_sexpr(packet, &mut out);
+
+    out
+}
+
+// Render the packets as a Graphviz DOT digraph, viewable with `dot -Tsvg`.
+pub fn dot(packet: &Packet) -> String {
+    let mut values = vec![];
+    calculate_all(packet, &mut values);
+
+    let mut out = String::from("digraph transmission {\n");
+    write_dot(packet, &mut 0, &mut values.into_iter(), &mut out);
+    out.push_str("}\n");
+
+    out
+}
+
+// Calculate the value of every packet, stored in prefix order. Packets that cannot be calculated,
+// such as unknown type IDs, have no value and neither do their parents.
+fn calculate_all(packet: &Packet, values: &mut Vec<Option<i64>>) -> Option<i64> {
+    let index = values.len();
+    values.push(None);
+
+    let value = if packet.class == 4 {
+        Some(packet.value)
+    } else {
+        let children: Vec<Option<i64>> = packet
+            .children
+            .iter()
+            .map(|child| calculate_all(child, values))
+            .collect();
+
+        children
+            .into_iter()
+            .collect::<Option<Vec<i64>>>()
+            .and_then(|children| operate(packet.class, &children))
+    };
+
+    values[index] = value;
+    value
+}
+
+// The value of a packet, or "?" if it couldn't be calculated.
+fn display(value: Option<i64>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "?".to_string(),
+    }
+}
+
+fn write_tree(
+    packet: &Packet,
+    depth: usize,
+    values: &mut impl Iterator<Item = Option<i64>>,
+    out: &mut String,
+) {
+    let value = display(values.next().flatten());
+    let label = if packet.class == 4 {
+        value
+    } else {
+        format!("{} = {}", symbol(packet.class), value)
+    };
+
+    writeln!(
+        out,
+        "{}{} (version {}, type {}, bits {}..{})",
+        "  ".repeat(depth),
+        label,
+        packet.version,
+        packet.class,
+        packet.bits.start,
+        packet.bits.end,
+    )
+    .unwrap();
+
+    for child in &packet.children {
+        write_tree(child, depth + 1, values, out);
+    }
+}
+
+fn write_sexpr(packet: &Packet, out: &mut String) {
+    if packet.class == 4 {
+        write!(out, "{}", packet.value).unwrap();
+        return;
+    }
+
+    write!(out, "({}", symbol(packet.class)).unwrap();
+    for child in &packet.children {
+        out.push(' ');
+        write_sexpr(child, out);
+    }
+    out.push(')');
+}
+
+// Writes the packet node and its edges, returning the node ID.
+fn write_dot(
+    packet: &Packet,
+    next_id: &mut usize,
+    values: &mut impl Iterator<Item = Option<i64>>,
+    out: &mut String,
+) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let value = display(values.next().flatten());
+    let (label, shape) = if packet.class == 4 {
+        (value, "box")
+    } else {
+        (format!("{}\\n= {}", symbol(packet.class), value), "ellipse")
+    };
+
+    writeln!(
+        out,
+        "    p{} [shape={}, label=\"{}\\nv{} bits {}..{}\"];",
+        id, shape, label, packet.version, packet.bits.start, packet.bits.end,
+    )
+    .unwrap();
+
+    for child in &packet.children {
+        let child_id = write_dot(child, next_id, values, out);
+        writeln!(out, "    p{} -> p{};", id, child_id).unwrap();
+    }
+
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn tree_test() {
+        assert_eq!(
+            tree(&parse("C200B40A82").unwrap()),
+            "+ = 3 (version 6, type 0, bits 0..40)\n\
+            \x20 1 (version 6, type 4, bits 18..29)\n\
+            \x20 2 (version 2, type 4, bits 29..40)\n"
+        );
+    }
+
+    #[test]
+    fn sexpr_test() {
+        assert_eq!(
+            sexpr(&parse("9C0141080250320F1802104A08").unwrap()),
+            "(== (+ 1 3) (* 2 2))"
+        );
+        assert_eq!(sexpr(&parse("CE00C43D881120").unwrap()), "(max 7 8 9)");
+    }
+
+    #[test]
+    fn dot_test() {
+        let graph = dot(&parse("C200B40A82").unwrap());
+
+        assert!(graph.starts_with("digraph transmission {\n"));
+        assert!(graph.contains("    p0 [shape=ellipse, label=\"+\\n= 3\\nv6 bits 0..40\"];\n"));
+        assert!(graph.contains("    p2 [shape=box, label=\"2\\nv2 bits 29..40\"];\n"));
+        assert!(graph.contains("    p0 -> p1;\n"));
+        assert!(graph.contains("    p0 -> p2;\n"));
+        assert!(graph.ends_with("}\n"));
+    }
+}
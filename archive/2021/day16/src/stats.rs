@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::parser::Packet;
+
+// Summary statistics of a packet and all of its sub packets.
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    // The sum of the version numbers of every packet.
+    pub version_total: i64,
+    // The number of packets on the longest path from the outermost packet to a literal, counting
+    // both.
+    pub depth: usize,
+    // The number of packets of each type ID.
+    pub types: BTreeMap<i64, usize>,
+    // The number of literal value packets.
+    pub literals: usize,
+    // The number of bits the outermost packet was decoded from, excluding any trailing padding.
+    pub bits: usize,
+}
+
+impl Stats {
+    // The total number of packets.
+    pub fn packets(&self) -> usize {
+        self.types.values().sum()
+    }
+}
+
+// Collect the statistics of a packet and all of its sub packets.
+pub fn stats(packet: &Packet) -> Stats {
+    let mut stats = Stats {
+        bits: packet.bits.len(),
+        ..Stats::default()
+    };
+    collect(packet, 1, &mut stats);
+
+    stats
+}
+
+fn collect(packet: &Packet, depth: usize, stats: &mut Stats) {
+    stats.version_total += packet.version;
+    stats.depth = stats.depth.max(depth);
+    *stats.types.entry(packet.class).or_insert(0) += 1;
+
+    if packet.class == 4 {
+        stats.literals += 1;
+    }
+
+    for child in &packet.children {
+        collect(child, depth + 1, stats);
+    }
+}
+
+// Render the statistics and the calculated value as a JSON object, the value is null if the
+// transmission couldn't be calculated.
+// Example:
+//   {"value": 3, "version_total": 14, "depth": 2, "packets": 3, "literals": 2, "bits": 40,
+//    "types": {"0": 1, "4": 2}}
+pub fn json(stats: &Stats, value: Option<i64>) -> String {
+    let value = match value {
+        Some(value) => value.to_string(),
+        None => "null".to_string(),
+    };
+    let types: Vec<String> = stats
+        .types
+        .iter()
+        .map(|(class, count)| format!("\"{}\": {}", class, count))
+        .collect();
+
+    let mut out = String::new();
+    write!(
+        out,
+        "{{\"value\": {}, \"version_total\": {}, \"depth\": {}, \"packets\": {}, \"literals\": {}, \
+        \"bits\": {}, \"types\": {{{}}}}}",
+        value,
+        stats.version_total,
+        stats.depth,
+        stats.packets(),
+        stats.literals,
+        stats.bits,
+        types.join(", "),
+    )
+    .unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn version_total_test() {
+        let totals = [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ];
+
+        for (data, total) in totals {
+            assert_eq!(
+                stats(&parse(data).unwrap()).version_total,
+                total,
+                "{}",
+                data
+            );
+        }
+    }
+
+    #[test]
+    fn stats_test() {
+        // Three nested operators around a single literal, four levels deep.
+        let nested = stats(&parse("8A004A801A8002F478").unwrap());
+        assert_eq!(nested.depth, 4);
+        assert_eq!(nested.literals, 1);
+        assert_eq!(nested.packets(), 4);
+        assert_eq!(nested.types, BTreeMap::from([(2, 3), (4, 1)]));
+
+        let sum = stats(&parse("C200B40A82").unwrap());
+        assert_eq!(sum.depth, 2);
+        assert_eq!(sum.bits, 40);
+        assert_eq!(sum.types, BTreeMap::from([(0, 1), (4, 2)]));
+    }
+
+    #[test]
+    fn json_test() {
+        let sum = stats(&parse("C200B40A82").unwrap());
+
+        assert_eq!(
+            json(&sum, Some(3)),
+            "{\"value\": 3, \"version_total\": 14, \"depth\": 2, \"packets\": 3, \"literals\": 2, \
+            \"bits\": 40, \"types\": {\"0\": 1, \"4\": 2}}"
+        );
+        assert!(json(&sum, None).starts_with("{\"value\": null,"));
+    }
+}
@@ -1,5 +1,8 @@
+use std::error::Error;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
 
 #[derive(Debug, PartialEq)]
 pub struct Target {
@@ -17,49 +20,190 @@ pub struct Probe {
     pub y_vel: i64,
 }
 
+// A malformed target description, with the 1-based column of the offending token.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
 // Generate target values from the input file.
-pub fn parse_file(path: &str) -> Target {
-    let file = File::open(path).unwrap();
+pub fn parse_file(path: &str) -> Result<Target, Box<dyn Error>> {
+    let file = File::open(path)?;
 
-    let lines: Vec<String> = io::BufReader::new(file)
-        .lines()
-        .map(|line| line.unwrap())
-        .collect();
+    let lines: Vec<String> = io::BufReader::new(file).lines().collect::<Result<_, _>>()?;
 
     if lines.len() != 1 {
-        panic!("{} lines found", lines.len());
+        return Err(format!("{} lines found instead of 1", lines.len()).into());
     }
 
-    parse(&lines[0])
+    Ok(parse(&lines[0])?)
 }
 
-// Generate the target values from a string.
-pub fn parse(data: &str) -> Target {
-    let words: Vec<&str> = data.split(" ").collect();
+// Generate the target values from a string like "target area: x=20..30, y=-10..-5".
+// Note: whitespace between tokens is optional, the axes can be in either order and the ends of
+//   a range can be reversed.
+pub fn parse(data: &str) -> Result<Target, ParseError> {
+    let mut cursor = Cursor { data, pos: 0 };
+
+    cursor.keyword("target")?;
+    cursor.keyword("area")?;
+    cursor.keyword(":")?;
 
-    if words.len() != 4 {
-        panic!("{} words found instead of 4: {:?}", words.len(), words);
+    let mut x_range = None;
+    let mut y_range = None;
+
+    for index in 0..2 {
+        if index > 0 {
+            cursor.keyword(",")?;
+        }
+
+        cursor.skip_whitespace();
+        let column = cursor.column();
+        let axis = cursor.axis()?;
+        cursor.keyword("=")?;
+        let range = cursor.range()?;
+
+        let slot = if axis == 'x' {
+            &mut x_range
+        } else {
+            &mut y_range
+        };
+        if slot.is_some() {
+            return Err(cursor.error_at(column, format!("duplicate {} range", axis)));
+        }
+        *slot = Some(range);
     }
 
-    if words[0] != "target" && words[1] != "area:" {
-        panic!("unknown line found {}", data);
+    cursor.skip_whitespace();
+    if cursor.pos < data.len() {
+        return Err(cursor.error("unexpected text after the target area"));
     }
 
-    let x_vals: Vec<&str> = words[2].split("=").collect::<Vec<&str>>()[1]
-        .split("..")
-        .collect();
-    let y_vals: Vec<&str> = words[3].split("=").collect::<Vec<&str>>()[1]
-        .split("..")
-        .collect();
+    // Both slots are filled, as two ranges were parsed without a duplicate.
+    let (x_min, x_max) = x_range.unwrap();
+    let (y_min, y_max) = y_range.unwrap();
+
+    Ok(Target {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+    })
+}
+
+// Position tracking over the target description for error reporting.
+struct Cursor<'a> {
+    data: &'a str,
+    pos: usize,
+}
 
-    let mut x_max = x_vals[1].chars();
-    x_max.next_back();
+impl Cursor<'_> {
+    fn rest(&self) -> &str {
+        &self.data[self.pos..]
+    }
+
+    fn column(&self) -> usize {
+        self.data[..self.pos].chars().count() + 1
+    }
 
-    Target {
-        x_min: x_vals[0].parse::<i64>().unwrap(),
-        x_max: x_max.as_str().parse::<i64>().unwrap(),
-        y_min: y_vals[0].parse::<i64>().unwrap(),
-        y_max: y_vals[1].parse::<i64>().unwrap(),
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.column(), message)
+    }
+
+    fn error_at(&self, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.data.len() - self.rest().trim_start().len();
+    }
+
+    // The next token, up to whitespace or punctuation, for error messages.
+    fn token(&self) -> &str {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == ',' || c == '=')
+            .unwrap_or(rest.len());
+
+        if end == 0 {
+            &rest[..rest.chars().next().map_or(0, char::len_utf8)]
+        } else {
+            &rest[..end]
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.token() {
+            "" => self.error(format!("expected {}, found end of line", expected)),
+            token => self.error(format!("expected {}, found {:?}", expected, token)),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        if self.rest().starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("{:?}", keyword)))
+        }
+    }
+
+    fn axis(&mut self) -> Result<char, ParseError> {
+        self.skip_whitespace();
+
+        match self.rest().chars().next() {
+            Some(axis @ ('x' | 'y')) => {
+                self.pos += 1;
+                Ok(axis)
+            }
+            _ => Err(self.unexpected("\"x\" or \"y\"")),
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64, ParseError> {
+        self.skip_whitespace();
+
+        // Take the whole word so that "3o" is reported as one bad token rather than a 3.
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '-' && c != '+')
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+
+        let digits = word.strip_prefix('-').unwrap_or(word);
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(self.unexpected("an integer"));
+        }
+
+        let value = word
+            .parse()
+            .map_err(|_| self.error(format!("{} is out of range", word)))?;
+        self.pos += end;
+
+        Ok(value)
+    }
+
+    // A range "a..b" as its lower and upper ends.
+    fn range(&mut self) -> Result<(i64, i64), ParseError> {
+        let first = self.integer()?;
+        self.keyword("..")?;
+        let second = self.integer()?;
+
+        Ok((first.min(second), first.max(second)))
     }
 }
 
@@ -77,6 +221,7 @@ pub fn in_target(probe: &Probe, target: &Target) -> bool {
 }
 
 // Checks if the probe has passed the target and cannot hit it anymore.
+#[allow(clippy::collapsible_if)]
 pub fn missed(probe: &Probe, target: &Target) -> bool {
     if probe.x_vel >= 0 {
         if probe.x_pos > target.x_max {
@@ -115,33 +260,241 @@ pub fn update(probe: &mut Probe) {
     probe.y_vel -= 1;
 }
 
-// Brute force checks a set of starting probes for the highest one
-pub fn find_highest_launch(target: &Target) -> i64 {
-    let mut highest = 0;
+// How a launch ended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    // The probe was inside the target after a step.
+    Hit,
+    // The probe passed beyond the far side of the target.
+    Overshot,
+    // The probe stalled, or was launched away, before reaching the near side of the target.
+    Undershot,
+    // The probe was above the target but fell past it between two steps.
+    FellThrough,
+}
 
-    for x in target.x_min..target.x_max {
-        for y in target.y_min..target.y_max {
-            let mut probe = Probe {
-                x_pos: 0,
-                y_pos: 0,
-                x_vel: x,
-                y_vel: y,
+// The path of a launched probe, from the launch position to the first hit or until it can no
+// longer hit.
+#[derive(Debug, PartialEq)]
+pub struct Trajectory {
+    pub positions: Vec<(i64, i64)>,
+    pub hit_step: Option<usize>,
+    pub apex: i64,
+    pub outcome: Outcome,
+}
+
+// Simulate the probe step by step until it hits or misses the target.
+pub fn simulate(mut probe: Probe, target: &Target) -> Trajectory {
+    let launch_x_vel = probe.x_vel;
+    let mut positions = vec![(probe.x_pos, probe.y_pos)];
+    let mut apex = probe.y_pos;
+
+    while !missed(&probe, target) {
+        update(&mut probe);
+        positions.push((probe.x_pos, probe.y_pos));
+        apex = apex.max(probe.y_pos);
+
+        if in_target(&probe, target) {
+            return Trajectory {
+                hit_step: Some(positions.len() - 1),
+                positions,
+                apex,
+                outcome: Outcome::Hit,
             };
+        }
+    }
 
-            while !missed(&probe, &target) {
-                if probe.y_pos > highest {
-                    highest = probe.y_pos;
-                }
+    let beyond = (launch_x_vel > 0 && probe.x_pos > target.x_max)
+        || (launch_x_vel < 0 && probe.x_pos < target.x_min);
+    let outcome = if beyond {
+        Outcome::Overshot
+    } else if probe.x_pos < target.x_min || probe.x_pos > target.x_max {
+        Outcome::Undershot
+    } else {
+        Outcome::FellThrough
+    };
+
+    Trajectory {
+        positions,
+        hit_step: None,
+        apex,
+        outcome,
+    }
+}
 
-                update(&mut probe);
+// Draw the trajectory and target as ASCII, with S for the launch, # for each position and T for
+// the target.
+pub fn plot(trajectory: &Trajectory, target: &Target) -> String {
+    let xs = trajectory.positions.iter().map(|(x, _)| *x);
+    let ys = trajectory.positions.iter().map(|(_, y)| *y);
+
+    let x_min = xs.clone().chain([target.x_min]).min().unwrap();
+    let x_max = xs.chain([target.x_max]).max().unwrap();
+    let y_min = ys.clone().chain([target.y_min]).min().unwrap();
+    let y_max = ys.chain([target.y_max]).max().unwrap();
+
+    let mut out = String::new();
+    for y in (y_min..=y_max).rev() {
+        for x in x_min..=x_max {
+            let tile = if trajectory.positions[0] == (x, y) {
+                'S'
+            } else if trajectory.positions.contains(&(x, y)) {
+                '#'
+            } else if x >= target.x_min
+                && x <= target.x_max
+                && y >= target.y_min
+                && y <= target.y_max
+            {
+                'T'
+            } else {
+                '.'
+            };
+
+            out.push(tile);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+// The highest point reached by any hitting launch, and every launch velocity that hits the target.
+#[derive(Debug, PartialEq)]
+pub struct Solution {
+    pub peak: i64,
+    pub velocities: Vec<(i64, i64)>,
+}
+
+// Solves for every launch velocity that hits the target without simulating each launch in full.
+// Each axis is independent, so the steps a velocity spends inside the target are found per axis
+// and a launch hits when an x step and a y step line up.
+// Note: None is returned if the target contains the launch height, as any launch that stalls
+//   inside it horizontally would hit on the way back down, however high it was launched.
+pub fn solve(target: &Target) -> Option<Solution> {
+    if target.y_min <= 0 && target.y_max >= 0 {
+        return None;
+    }
+
+    let x_windows: Vec<(i64, (i64, Option<i64>))> = x_velocities(target)
+        .filter_map(|x_vel| Some((x_vel, x_steps(x_vel, target)?)))
+        .collect();
+
+    let mut velocities = vec![];
+    for y_vel in y_velocities(target) {
+        let steps = y_steps(y_vel, target);
+
+        for (x_vel, (first, last)) in &x_windows {
+            let hits = steps
+                .iter()
+                .any(|step| step >= first && last.is_none_or(|last| *step <= last));
+
+            if hits {
+                velocities.push((*x_vel, y_vel));
             }
         }
     }
 
-    highest
+    let peak = velocities
+        .iter()
+        .map(|(_, y_vel)| apex(*y_vel))
+        .max()
+        .unwrap_or(0);
+
+    Some(Solution { peak, velocities })
+}
+
+// The height a probe launched with the y velocity rises to.
+pub fn apex(y_vel: i64) -> i64 {
+    if y_vel > 0 {
+        y_vel * (y_vel + 1) / 2
+    } else {
+        0
+    }
+}
+
+// The smallest speed whose triangular number, the distance travelled before stalling, reaches the
+// distance.
+fn stall_speed(distance: i64) -> i64 {
+    let mut speed = ((2.0 * distance as f64).sqrt() as i64 - 1).max(0);
+    while speed * (speed + 1) / 2 < distance {
+        speed += 1;
+    }
+
+    speed
+}
+
+// The x velocities that could reach the target. Drag stops the probe after travelling the
+// triangular number of its speed, so anything slower than the stall speed of the near edge falls
+// short and anything faster than the far edge overshoots on the first step.
+pub fn x_velocities(target: &Target) -> RangeInclusive<i64> {
+    if target.x_min > 0 {
+        stall_speed(target.x_min)..=target.x_max
+    } else if target.x_max < 0 {
+        target.x_min..=-stall_speed(-target.x_max)
+    } else {
+        target.x_min..=target.x_max
+    }
+}
+
+// The y velocities that could reach the target. A probe launched upwards at v comes back down
+// through the launch height at -(v + 1), so for a target below the launch the fastest useful
+// launch lands on the bottom edge one step later. For a target above, the launch must be fast
+// enough to rise to the bottom edge and not so fast it passes the top edge in the first step.
+// Note: targets containing the launch height are not bounded.
+pub fn y_velocities(target: &Target) -> RangeInclusive<i64> {
+    if target.y_max < 0 {
+        target.y_min..=-target.y_min - 1
+    } else {
+        stall_speed(target.y_min)..=target.y_max
+    }
+}
+
+// The first and last steps the x velocity spends inside the target's x range, with no last step
+// if the probe stalls inside it.
+fn x_steps(x_vel: i64, target: &Target) -> Option<(i64, Option<i64>)> {
+    let (mut x_pos, mut x_vel) = (0, x_vel);
+    let mut first = None;
+    let mut step = 0;
+
+    loop {
+        x_pos += x_vel;
+        x_vel -= x_vel.signum();
+        step += 1;
+
+        let inside = x_pos >= target.x_min && x_pos <= target.x_max;
+        if inside && first.is_none() {
+            first = Some(step);
+        }
+
+        // The probe only moves in one direction, so once it leaves the target it can't return.
+        if x_vel == 0 || (!inside && first.is_some()) {
+            let last = if inside { None } else { Some(step - 1) };
+            return first.map(|first| (first, last));
+        }
+    }
+}
+
+// Every step the y velocity spends inside the target's y range.
+fn y_steps(y_vel: i64, target: &Target) -> Vec<i64> {
+    let (mut y_pos, mut y_vel) = (0, y_vel);
+    let mut steps = vec![];
+    let mut step = 0;
+
+    while y_vel >= 0 || y_pos >= target.y_min {
+        if step > 0 && y_pos >= target.y_min && y_pos <= target.y_max {
+            steps.push(step);
+        }
+
+        y_pos += y_vel;
+        y_vel -= 1;
+        step += 1;
+    }
+
+    steps
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -149,12 +502,54 @@ mod tests {
     fn parse_test() {
         assert_eq!(
             parse("target area: x=1..14, y=15..29"),
-            Target {
+            Ok(Target {
                 x_min: 1,
                 x_max: 14,
                 y_min: 15,
                 y_max: 29
-            }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_variations_test() {
+        let expected = Ok(Target {
+            x_min: 20,
+            x_max: 30,
+            y_min: -10,
+            y_max: -5,
+        });
+
+        assert_eq!(parse("target area: x=20..30, y=-10..-5"), expected);
+        assert_eq!(
+            parse("  target   area :x = 20 .. 30 ,y=-10..-5  "),
+            expected
+        );
+        assert_eq!(parse("target area: y=-10..-5, x=20..30"), expected);
+        assert_eq!(parse("target area: x=30..20, y=-5..-10"), expected);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let column = |data| parse(data).unwrap_err().column;
+
+        assert_eq!(column("target zone: x=20..30, y=-10..-5"), 8);
+        assert_eq!(column("target area: z=20..30, y=-10..-5"), 14);
+        assert_eq!(column("target area: x=20..3o, y=-10..-5"), 20);
+        assert_eq!(column("target area: x=20..30 y=-10..-5"), 23);
+        assert_eq!(column("target area: x=20..30, x=-10..-5"), 24);
+        assert_eq!(column("target area: x=20..30, y=-10"), 29);
+        assert_eq!(column("target area: x=20..30, y=-10..-5 z"), 34);
+        assert_eq!(
+            column("target area: x=20..30, y=-10..99999999999999999999"),
+            31
+        );
+
+        assert_eq!(
+            parse("target area: x=20..3o, y=-10..-5")
+                .unwrap_err()
+                .to_string(),
+            "column 20: expected an integer, found \"3o\""
         );
     }
 
@@ -225,4 +620,139 @@ mod tests {
         probe.y_vel = 0;
         assert_eq!(missed(&probe, &target), true);
     }
+
+    #[test]
+    fn velocity_ranges_test() {
+        let target = parse("target area: x=20..30, y=-10..-5").unwrap();
+
+        assert_eq!(x_velocities(&target), 6..=30);
+        assert_eq!(y_velocities(&target), -10..=9);
+    }
+
+    #[test]
+    fn solve_test() {
+        let target = parse("target area: x=20..30, y=-10..-5").unwrap();
+        let solution = solve(&target).unwrap();
+
+        assert_eq!(solution.peak, 45);
+        assert_eq!(solution.velocities.len(), 112);
+        assert!(solution.velocities.contains(&(6, 9)));
+        assert!(solution.velocities.contains(&(30, -10)));
+
+        // Every velocity found hits when simulated step by step.
+        for (x_vel, y_vel) in &solution.velocities {
+            let mut probe = Probe {
+                x_pos: 0,
+                y_pos: 0,
+                x_vel: *x_vel,
+                y_vel: *y_vel,
+            };
+
+            while !in_target(&probe, &target) {
+                assert!(!missed(&probe, &target), "{:?} missed", (x_vel, y_vel));
+                update(&mut probe);
+            }
+        }
+
+        // A mirrored target has mirrored solutions.
+        let mirrored = Target {
+            x_min: -30,
+            x_max: -20,
+            y_min: -10,
+            y_max: -5,
+        };
+        let solution = solve(&mirrored).unwrap();
+        assert_eq!(solution.peak, 45);
+        assert_eq!(solution.velocities.len(), 112);
+        assert!(solution.velocities.contains(&(-6, 9)));
+    }
+
+    #[test]
+    fn solve_above_test() {
+        let target = Target {
+            x_min: 5,
+            x_max: 7,
+            y_min: 2,
+            y_max: 3,
+        };
+        let solution = solve(&target).unwrap();
+
+        // Launching (3, 3) stalls at x = 6 and passes y = 3 on the way up and down.
+        assert!(solution.velocities.contains(&(3, 3)));
+        assert_eq!(solution.peak, 6);
+        assert!(solution.velocities.iter().all(|(_, y_vel)| *y_vel > 0));
+
+        // A target containing the launch height has no highest launch.
+        let target = Target {
+            x_min: 5,
+            x_max: 7,
+            y_min: -2,
+            y_max: 3,
+        };
+        assert_eq!(solve(&target), None);
+    }
+
+    fn launch(x_vel: i64, y_vel: i64) -> Probe {
+        Probe {
+            x_pos: 0,
+            y_pos: 0,
+            x_vel,
+            y_vel,
+        }
+    }
+
+    #[test]
+    fn simulate_test() {
+        let target = parse("target area: x=20..30, y=-10..-5").unwrap();
+
+        let trajectory = simulate(launch(7, 2), &target);
+        assert_eq!(trajectory.outcome, Outcome::Hit);
+        assert_eq!(trajectory.hit_step, Some(7));
+        assert_eq!(trajectory.apex, 3);
+        assert_eq!(trajectory.positions.len(), 8);
+        assert_eq!(trajectory.positions[7], (28, -7));
+
+        let trajectory = simulate(launch(17, -4), &target);
+        assert_eq!(trajectory.outcome, Outcome::Overshot);
+        assert_eq!(trajectory.hit_step, None);
+
+        // Stalls at x = 3 after two steps, the simulation stops on the way up.
+        let trajectory = simulate(launch(2, 5), &target);
+        assert_eq!(trajectory.outcome, Outcome::Undershot);
+        assert_eq!(trajectory.positions, vec![(0, 0), (2, 5), (3, 9)]);
+        assert_eq!(trajectory.apex, 9);
+
+        // Stalls above the target then drops from y = 0 straight to y = -11.
+        let trajectory = simulate(launch(6, 10), &target);
+        assert_eq!(trajectory.outcome, Outcome::FellThrough);
+        assert_eq!(trajectory.apex, apex(10));
+    }
+
+    #[test]
+    fn plot_test() {
+        let target = parse("target area: x=20..30, y=-10..-5").unwrap();
+        let trajectory = simulate(launch(7, 2), &target);
+
+        assert_eq!(
+            plot(&trajectory, &target),
+            [
+                ".............#....#............",
+                ".......#..............#........",
+                "...............................",
+                "S........................#.....",
+                "...............................",
+                "...............................",
+                "...........................#...",
+                "...............................",
+                "....................TTTTTTTTTTT",
+                "....................TTTTTTTTTTT",
+                "....................TTTTTTTT#TT",
+                "....................TTTTTTTTTTT",
+                "....................TTTTTTTTTTT",
+                "....................TTTTTTTTTTT",
+                "",
+            ]
+            .join("\n")
+        );
+    }
 }
@@ -1,3 +1,41 @@
+use std::env;
+
+use day17::*;
+
+// Finds the highest launch and the number of launches that hit the target. The trajectory of the
+// highest launch can be drawn with the optional --plot argument.
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let show_plot = match args.first().map(String::as_str) {
+        None => false,
+        Some("--plot") => true,
+        Some(arg) => panic!("unknown argument {}, expected --plot", arg),
+    };
+
+    let target = parse_file("assets/input.txt").expect("invalid input");
+    let solution = solve(&target).expect("target contains the launch height");
+
+    println!("the highest launch reaches {}", solution.peak);
+    println!(
+        "{} distinct launch velocities hit the target",
+        solution.velocities.len()
+    );
+
+    if show_plot {
+        if let Some((x_vel, y_vel)) = solution
+            .velocities
+            .iter()
+            .find(|(_, y_vel)| apex(*y_vel) == solution.peak)
+        {
+            let probe = Probe {
+                x_pos: 0,
+                y_pos: 0,
+                x_vel: *x_vel,
+                y_vel: *y_vel,
+            };
+
+            println!("launching at {},{}:", x_vel, y_vel);
+            print!("{}", plot(&simulate(probe, &target), &target));
+        }
+    }
 }
@@ -2,8 +2,8 @@ use day17::*;
 
 #[test]
 fn provieded_tests() {
-    assert_eq!(
-        find_highest_launch(&parse("target area: x=20..30, y=-10..-5")),
-        45
-    );
+    let solution = solve(&parse("target area: x=20..30, y=-10..-5").unwrap()).unwrap();
+
+    assert_eq!(solution.peak, 45);
+    assert_eq!(solution.velocities.len(), 112);
 }
@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::grid::*;
+use crate::search::*;
+
+// Find up to k loopless paths from start to end in order of increasing cost, using Yen's
+// algorithm. The first path is the shortest path, each following one is the cheapest path that
+// deviates from the paths already found.
+// Note: each new path runs an A* search from every point on the previous path, so large k on
+//   large grids is slow.
+pub fn k_shortest<G: Grid>(
+    grid: &G,
+    start: Point,
+    end: Point,
+    neighbors: Neighbors,
+    k: usize,
+) -> Vec<Path> {
+    let mut found = match shortest(grid, start, end, neighbors, Algorithm::AStar) {
+        Some(path) if k > 0 => vec![path],
+        _ => return vec![],
+    };
+
+    let mut candidates = BinaryHeap::<Reverse<(u64, Vec<Point>)>>::new();
+    let mut seen = HashSet::<Vec<Point>>::from([found[0].points.clone()]);
+
+    while found.len() < k {
+        let previous = &found[found.len() - 1].points;
+        let mut root_cost = 0;
+
+        // Deviate from the previous path at every point before the end.
+        for spur_index in 0..previous.len() - 1 {
+            let root = &previous[..=spur_index];
+            let spur = previous[spur_index];
+
+            if spur_index > 0 {
+                root_cost += grid.cost(previous[spur_index - 1], spur);
+            }
+
+            // Block the next move of every found path sharing this root, and the root itself so
+            // the new path can't loop back through it.
+            let blocked_moves: HashSet<(Point, Point)> = found
+                .iter()
+                .filter(|path| {
+                    path.points.len() > spur_index + 1 && path.points[..=spur_index] == *root
+                })
+                .map(|path| (spur, path.points[spur_index + 1]))
+                .collect();
+            let blocked_points: HashSet<Point> = root[..spur_index].iter().copied().collect();
+            let allowed =
+                |from, to| !blocked_points.contains(&to) && !blocked_moves.contains(&(from, to));
+
+            let mut search = Search::new(grid);
+            let heuristic = heuristic(grid, end, neighbors);
+            if !search.weighted(grid, spur, end, neighbors, heuristic, allowed, &mut |_| {}) {
+                continue;
+            }
+
+            let spur_path = search.path(grid, spur, end);
+            let mut points = root[..spur_index].to_vec();
+            points.extend(spur_path.points);
+
+            if seen.insert(points.clone()) {
+                candidates.push(Reverse((root_cost + spur_path.cost, points)));
+            }
+        }
+
+        match candidates.pop() {
+            Some(Reverse((cost, points))) => found.push(Path { points, cost }),
+            None => break,
+        }
+    }
+
+    found
+}
+
+// Find every path from start to end that ties for the lowest cost, up to the limit. The paths
+// are ordered by their points.
+// Note: paths are only guaranteed to be complete when every move costs at least 1, ties that
+//   depend on zero cost moves may be missed.
+pub fn all_shortest<G: Grid>(
+    grid: &G,
+    start: Point,
+    end: Point,
+    neighbors: Neighbors,
+    limit: usize,
+) -> Vec<Path> {
+    if !valid(grid, start) || !valid(grid, end) {
+        return vec![];
+    }
+
+    let mut search = Search::new(grid);
+    if !search.weighted(grid, start, end, neighbors, |_| 0, |_, _| true, &mut |_| {}) {
+        return vec![];
+    }
+
+    // Walk back from the end through every neighbor that lies on an optimal route, which is any
+    // point whose cost plus the move equals the cost of the point being left.
+    let cost = search.cost[search.index(end)];
+    let mut paths = vec![];
+    let mut stack = vec![vec![end]];
+
+    while let Some(reversed) = stack.pop() {
+        if paths.len() >= limit {
+            break;
+        }
+
+        let current = reversed[reversed.len() - 1];
+        if current == start {
+            let mut points = reversed;
+            points.reverse();
+            paths.push(Path { points, cost });
+            continue;
+        }
+
+        let current_cost = search.cost[search.index(current)];
+        for previous in neighbors.of(grid, current) {
+            let previous_cost = search.cost[search.index(previous)];
+            if previous_cost != u64::MAX
+                && previous_cost + grid.cost(previous, current) == current_cost
+            {
+                let mut next = reversed.clone();
+                next.push(previous);
+                stack.push(next);
+            }
+        }
+    }
+
+    paths.sort_by(|a, b| a.points.cmp(&b.points));
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_shortest_test() {
+        let grid = vec![vec![1; 3]; 3];
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 2, y: 2 };
+
+        let paths = k_shortest(&grid, start, end, Neighbors::Four, 8);
+        let costs: Vec<u64> = paths.iter().map(|path| path.cost).collect();
+
+        // There are six ways to make two moves right and two moves down, then detours.
+        assert_eq!(costs, vec![4, 4, 4, 4, 4, 4, 6, 6]);
+
+        for path in &paths {
+            let unique: HashSet<&Point> = path.points.iter().collect();
+            assert_eq!(unique.len(), path.points.len(), "loop in {:?}", path);
+            assert_eq!(path.points.first(), Some(&start));
+            assert_eq!(path.points.last(), Some(&end));
+        }
+
+        let unique: HashSet<&Vec<Point>> = paths.iter().map(|path| &path.points).collect();
+        assert_eq!(unique.len(), paths.len());
+    }
+
+    #[test]
+    fn k_shortest_exhausted() {
+        // A single corridor only has one loopless path.
+        let grid = vec![vec![1, 1, 1]];
+        let paths = k_shortest(
+            &grid,
+            Point { x: 0, y: 0 },
+            Point { x: 2, y: 0 },
+            Neighbors::Four,
+            3,
+        );
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].cost, 2);
+    }
+
+    #[test]
+    fn all_shortest_test() {
+        let grid = vec![vec![1, 1, 1], vec![1, 5, 1], vec![1, 1, 1]];
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 2, y: 2 };
+
+        // Only the two paths around the edge avoid the expensive middle.
+        let paths = all_shortest(&grid, start, end, Neighbors::Four, 100);
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|path| path.cost == 4));
+        assert_eq!(paths[0].points[1], Point { x: 0, y: 1 });
+        assert_eq!(paths[1].points[1], Point { x: 1, y: 0 });
+
+        let grid = vec![vec![1; 3]; 3];
+        assert_eq!(
+            all_shortest(&grid, start, end, Neighbors::Four, 100).len(),
+            6
+        );
+        assert_eq!(all_shortest(&grid, start, end, Neighbors::Four, 4).len(), 4);
+    }
+}
@@ -0,0 +1,142 @@
+// A position on a grid, with the origin in the top left corner.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+// A rectangular grid that can be searched for paths.
+pub trait Grid {
+    // The width and height of the grid.
+    fn dimensions(&self) -> (usize, usize);
+
+    // The cost of moving from a point to an adjacent point.
+    fn cost(&self, from: Point, to: Point) -> u64;
+
+    // Whether the point can be moved onto at all.
+    fn passable(&self, _point: Point) -> bool {
+        true
+    }
+
+    // A lower bound on the cost of any single move, used to scale the A* heuristic.
+    // Note: the heuristic is only admissible if no move is cheaper than this.
+    fn min_cost(&self) -> u64 {
+        1
+    }
+}
+
+// A plain grid of cells where each cell holds the cost of moving onto it, e.g. a risk or height
+// map. Negative cells are impassable.
+impl Grid for Vec<Vec<i32>> {
+    fn dimensions(&self) -> (usize, usize) {
+        (self.first().map_or(0, |row| row.len()), self.len())
+    }
+
+    fn cost(&self, _from: Point, to: Point) -> u64 {
+        self[to.y][to.x] as u64
+    }
+
+    fn passable(&self, point: Point) -> bool {
+        self[point.y][point.x] >= 0
+    }
+
+    fn min_cost(&self) -> u64 {
+        self.iter()
+            .flatten()
+            .filter(|cost| **cost >= 0)
+            .min()
+            .map_or(0, |cost| *cost as u64)
+    }
+}
+
+// The set of moves allowed from a point.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Neighbors {
+    // Up, down, left and right.
+    Four,
+    // The four directions plus diagonals.
+    Eight,
+}
+
+impl Neighbors {
+    // The x and y offsets of each move.
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+
+    // The minimum number of moves between two points, ignoring costs and impassable points.
+    pub fn distance(&self, from: Point, to: Point) -> u64 {
+        let dx = from.x.abs_diff(to.x) as u64;
+        let dy = from.y.abs_diff(to.y) as u64;
+
+        match self {
+            Self::Four => dx + dy,
+            Self::Eight => dx.max(dy),
+        }
+    }
+
+    // The passable points that can be moved to from the given point.
+    pub fn of<'a, G: Grid>(&self, grid: &'a G, point: Point) -> impl Iterator<Item = Point> + 'a {
+        let (width, height) = grid.dimensions();
+
+        self.offsets().iter().filter_map(move |(dx, dy)| {
+            let next = Point {
+                x: point.x.checked_add_signed(*dx)?,
+                y: point.y.checked_add_signed(*dy)?,
+            };
+
+            if next.x < width && next.y < height && grid.passable(next) {
+                Some(next)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_test() {
+        let grid = vec![vec![1, 1, 1], vec![1, -1, 1], vec![1, 1, 1]];
+        let corner = Point { x: 0, y: 0 };
+        let edge = Point { x: 1, y: 0 };
+
+        assert_eq!(
+            Neighbors::Four.of(&grid, corner).collect::<Vec<Point>>(),
+            vec![Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]
+        );
+        assert_eq!(
+            Neighbors::Eight.of(&grid, edge).collect::<Vec<Point>>(),
+            vec![
+                Point { x: 0, y: 0 },
+                Point { x: 2, y: 0 },
+                Point { x: 0, y: 1 },
+                Point { x: 2, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn distance_test() {
+        let from = Point { x: 1, y: 5 };
+        let to = Point { x: 4, y: 1 };
+
+        assert_eq!(Neighbors::Four.distance(from, to), 7);
+        assert_eq!(Neighbors::Eight.distance(from, to), 4);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod alternates;
+pub mod grid;
+pub mod search;
@@ -0,0 +1,349 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::grid::*;
+
+// The search algorithm used to find a path.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Algorithm {
+    // Explores points in order of the cheapest known cost.
+    Dijkstra,
+    // Dijkstra guided towards the end with a distance heuristic.
+    AStar,
+    // Explores points in order of the number of moves, only optimal for unit costs.
+    Bfs,
+}
+
+// A path between two points, including both, and the total cost of its moves.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path {
+    pub points: Vec<Point>,
+    pub cost: u64,
+}
+
+// A change to the search state, reported in the order the search makes them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+    // The point was added to the frontier, or its known cost was lowered.
+    Opened(Point),
+    // The point was taken off the frontier and its neighbors explored.
+    Closed(Point),
+}
+
+// Find the cheapest path from start to end, or None if the end can't be reached.
+// Note: the A* heuristic is the move distance (Manhattan for four neighbors, Chebyshev for eight)
+//   scaled by the grid's minimum move cost, so it never overestimates.
+pub fn shortest<G: Grid>(
+    grid: &G,
+    start: Point,
+    end: Point,
+    neighbors: Neighbors,
+    algorithm: Algorithm,
+) -> Option<Path> {
+    shortest_observed(grid, start, end, neighbors, algorithm, |_| {})
+}
+
+// Find the cheapest path like shortest, reporting every step of the search to the observer.
+pub fn shortest_observed<G: Grid>(
+    grid: &G,
+    start: Point,
+    end: Point,
+    neighbors: Neighbors,
+    algorithm: Algorithm,
+    mut observe: impl FnMut(Step),
+) -> Option<Path> {
+    if !valid(grid, start) || !valid(grid, end) {
+        return None;
+    }
+
+    let mut search = Search::new(grid);
+    let everything = |_, _| true;
+
+    let found = match algorithm {
+        Algorithm::Dijkstra => {
+            search.weighted(grid, start, end, neighbors, |_| 0, everything, &mut observe)
+        }
+        Algorithm::AStar => {
+            let heuristic = heuristic(grid, end, neighbors);
+            search.weighted(
+                grid,
+                start,
+                end,
+                neighbors,
+                heuristic,
+                everything,
+                &mut observe,
+            )
+        }
+        Algorithm::Bfs => search.breadth_first(grid, start, end, neighbors, &mut observe),
+    };
+
+    if found {
+        Some(search.path(grid, start, end))
+    } else {
+        None
+    }
+}
+
+// Whether the point is on the grid and passable.
+pub(crate) fn valid<G: Grid>(grid: &G, point: Point) -> bool {
+    let (width, height) = grid.dimensions();
+    point.x < width && point.y < height && grid.passable(point)
+}
+
+// The A* heuristic, the move distance to the end scaled by the grid's minimum move cost.
+pub(crate) fn heuristic<G: Grid>(
+    grid: &G,
+    end: Point,
+    neighbors: Neighbors,
+) -> impl Fn(Point) -> u64 {
+    let scale = grid.min_cost();
+    move |point| neighbors.distance(point, end) * scale
+}
+
+// The search state, stored densely by the index of each point.
+pub(crate) struct Search {
+    width: usize,
+    came_from: Vec<usize>,
+    // The cheapest known cost from the start, only filled in by weighted searches.
+    pub(crate) cost: Vec<u64>,
+}
+
+impl Search {
+    pub(crate) fn new<G: Grid>(grid: &G) -> Self {
+        let (width, height) = grid.dimensions();
+
+        Search {
+            width,
+            came_from: vec![usize::MAX; width * height],
+            cost: vec![u64::MAX; width * height],
+        }
+    }
+
+    pub(crate) fn index(&self, point: Point) -> usize {
+        point.y * self.width + point.x
+    }
+
+    fn point(&self, index: usize) -> Point {
+        Point {
+            x: index % self.width,
+            y: index / self.width,
+        }
+    }
+
+    // Dijkstra's algorithm ordered by the known cost plus the heuristic. Points are pushed again
+    // whenever a cheaper route is found and stale heap entries are skipped when popped. Only the
+    // moves between points that are allowed are searched.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn weighted<G: Grid>(
+        &mut self,
+        grid: &G,
+        start: Point,
+        end: Point,
+        neighbors: Neighbors,
+        heuristic: impl Fn(Point) -> u64,
+        allowed: impl Fn(Point, Point) -> bool,
+        observe: &mut impl FnMut(Step),
+    ) -> bool {
+        let mut closed = vec![false; self.came_from.len()];
+        let mut open = BinaryHeap::new();
+
+        let start_index = self.index(start);
+        self.cost[start_index] = 0;
+        open.push(Reverse((heuristic(start), start_index)));
+        observe(Step::Opened(start));
+
+        while let Some(Reverse((_, index))) = open.pop() {
+            if closed[index] {
+                continue;
+            }
+            closed[index] = true;
+
+            let current = self.point(index);
+            observe(Step::Closed(current));
+            if current == end {
+                return true;
+            }
+
+            for next in neighbors.of(grid, current) {
+                let next_index = self.index(next);
+                if closed[next_index] || !allowed(current, next) {
+                    continue;
+                }
+
+                let next_cost = self.cost[index] + grid.cost(current, next);
+                if next_cost < self.cost[next_index] {
+                    self.cost[next_index] = next_cost;
+                    self.came_from[next_index] = index;
+                    open.push(Reverse((next_cost + heuristic(next), next_index)));
+                    observe(Step::Opened(next));
+                }
+            }
+        }
+
+        false
+    }
+
+    fn breadth_first<G: Grid>(
+        &mut self,
+        grid: &G,
+        start: Point,
+        end: Point,
+        neighbors: Neighbors,
+        observe: &mut impl FnMut(Step),
+    ) -> bool {
+        let mut seen = vec![false; self.came_from.len()];
+        let mut open = VecDeque::from([start]);
+        seen[self.index(start)] = true;
+        observe(Step::Opened(start));
+
+        while let Some(current) = open.pop_front() {
+            observe(Step::Closed(current));
+            if current == end {
+                return true;
+            }
+
+            for next in neighbors.of(grid, current) {
+                let next_index = self.index(next);
+                if !seen[next_index] {
+                    seen[next_index] = true;
+                    self.came_from[next_index] = self.index(current);
+                    open.push_back(next);
+                    observe(Step::Opened(next));
+                }
+            }
+        }
+
+        false
+    }
+
+    // Build the path by walking back from the end, summing the move costs on the way.
+    pub(crate) fn path<G: Grid>(&self, grid: &G, start: Point, end: Point) -> Path {
+        let mut points = vec![end];
+        let mut cost = 0;
+        let mut current = end;
+
+        while current != start {
+            let previous = self.point(self.came_from[self.index(current)]);
+            cost += grid.cost(previous, current);
+            points.push(previous);
+            current = previous;
+        }
+
+        points.reverse();
+
+        Path { points, cost }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<Vec<i32>> {
+        [
+            "1163751742",
+            "1381373672",
+            "2136511328",
+            "3694931569",
+            "7463417111",
+            "1319128137",
+            "1359912421",
+            "3125421639",
+            "1293138521",
+            "2311944581",
+        ]
+        .iter()
+        .map(|line| {
+            line.chars()
+                .map(|c| c.to_digit(10).unwrap() as i32)
+                .collect()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn weighted_test() {
+        let map = example();
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 9, y: 9 };
+
+        for algorithm in [Algorithm::Dijkstra, Algorithm::AStar] {
+            let path = shortest(&map, start, end, Neighbors::Four, algorithm).unwrap();
+
+            assert_eq!(path.cost, 40);
+            assert_eq!(path.points.first(), Some(&start));
+            assert_eq!(path.points.last(), Some(&end));
+            assert_eq!(path.points.len(), 19);
+        }
+
+        let dijkstra = shortest(&map, start, end, Neighbors::Eight, Algorithm::Dijkstra).unwrap();
+        let a_star = shortest(&map, start, end, Neighbors::Eight, Algorithm::AStar).unwrap();
+        assert!(dijkstra.cost < 40);
+        assert_eq!(dijkstra.cost, a_star.cost);
+    }
+
+    #[test]
+    fn breadth_first_test() {
+        let maze = vec![vec![1, -1, 1, 1], vec![1, -1, 1, -1], vec![1, 1, 1, 1]];
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 3, y: 0 };
+
+        let path = shortest(&maze, start, end, Neighbors::Four, Algorithm::Bfs);
+        assert_eq!(path.map(|path| path.cost), Some(7));
+
+        let path = shortest(&maze, start, end, Neighbors::Eight, Algorithm::Bfs);
+        assert_eq!(path.map(|path| path.cost), Some(4));
+    }
+
+    #[test]
+    fn observed_test() {
+        let map = example();
+        let start = Point { x: 0, y: 0 };
+        let end = Point { x: 9, y: 9 };
+
+        let mut closed = [0, 0];
+        for (index, algorithm) in [Algorithm::Dijkstra, Algorithm::AStar].iter().enumerate() {
+            let mut steps = vec![];
+            shortest_observed(&map, start, end, Neighbors::Four, *algorithm, |step| {
+                steps.push(step)
+            });
+
+            assert_eq!(steps.first(), Some(&Step::Opened(start)));
+            assert_eq!(steps.last(), Some(&Step::Closed(end)));
+            closed[index] = steps
+                .iter()
+                .filter(|step| matches!(step, Step::Closed(_)))
+                .count();
+        }
+
+        // The heuristic lets A* finish without exploring as much of the map.
+        assert!(closed[1] < closed[0]);
+    }
+
+    #[test]
+    fn unreachable_test() {
+        let walled = vec![vec![1, -1, 1], vec![1, -1, 1]];
+        let start = Point { x: 0, y: 0 };
+
+        for algorithm in [Algorithm::Dijkstra, Algorithm::AStar, Algorithm::Bfs] {
+            let end = Point { x: 2, y: 1 };
+            assert_eq!(
+                shortest(&walled, start, end, Neighbors::Four, algorithm),
+                None
+            );
+
+            let end = Point { x: 1, y: 1 };
+            assert_eq!(
+                shortest(&walled, start, end, Neighbors::Four, algorithm),
+                None
+            );
+
+            let end = Point { x: 5, y: 0 };
+            assert_eq!(
+                shortest(&walled, start, end, Neighbors::Four, algorithm),
+                None
+            );
+        }
+    }
+}
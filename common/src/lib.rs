@@ -0,0 +1,19 @@
+/// Shared pieces of the daily puzzles, currently the Solution trait implemented by every day and
+/// run by the aoc binary.
+use std::error::Error;
+
+// A puzzle solution for a single day. The input is parsed once and then shared by both parts.
+// Note: answers are returned as strings since some days answer with text rather than a number.
+pub trait Solution {
+    // The parsed puzzle input.
+    type Input;
+
+    // Parse the contents of an input file.
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>>;
+
+    // Solve the first part of the puzzle.
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>>;
+
+    // Solve the second part of the puzzle.
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>>;
+}
@@ -0,0 +1,167 @@
+/// Counts the calories carried by each elf to find the elves carrying the most.
+/// Notes:
+///   - Each input line is the calories of a single food item, the items carried by each elf are
+///     separated by one or more blank lines.
+///   - The first part is the highest total and the second part is the sum of the top three.
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::io::BufRead;
+
+use common::Solution;
+
+// The total calories carried by an elf, the index is the elf's position in the input starting
+// from 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elf {
+    pub index: usize,
+    pub total: i32,
+}
+
+// Elves are ordered by their total, ties are broken in favor of the elf that came first.
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total
+            .cmp(&other.total)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Keeps the highest N values pushed to it, using a min-heap of at most N values so the lowest
+// value kept can be replaced in O(log N).
+pub struct TopN<T: Ord> {
+    limit: usize,
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+impl<T: Ord> TopN<T> {
+    pub fn new(limit: usize) -> Self {
+        TopN {
+            limit,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    // Add a value, dropping the lowest value kept if there are now more than N.
+    pub fn push(&mut self, value: T) {
+        if self.heap.len() < self.limit {
+            self.heap.push(Reverse(value));
+        } else if let Some(mut lowest) = self.heap.peek_mut() {
+            if value > lowest.0 {
+                *lowest = Reverse(value);
+            }
+        }
+    }
+
+    // The values kept, highest first.
+    pub fn into_sorted(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(value)| value)
+            .collect()
+    }
+}
+
+// Read the food items carried by each elf and find the N elves carrying the most calories,
+// highest first. Only the top N totals are kept in memory.
+pub fn top_elves(input: impl BufRead, limit: usize) -> Result<Vec<Elf>, Box<dyn Error>> {
+    let mut top = TopN::new(limit);
+    let mut index = 0;
+    let mut current: Option<i32> = None;
+
+    for (number, raw) in input.lines().enumerate() {
+        let line = raw?;
+
+        if line.trim().is_empty() {
+            if let Some(total) = current.take() {
+                top.push(Elf { index, total });
+                index += 1;
+            }
+            continue;
+        }
+
+        let calories: i32 = line
+            .trim()
+            .parse()
+            .map_err(|err| format!("line {}: {:?} {}", number + 1, line, err))?;
+        *current.get_or_insert(0) += calories;
+    }
+
+    if let Some(total) = current {
+        top.push(Elf { index, total });
+    }
+
+    Ok(top.into_sorted())
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Elf>;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        top_elves(data.as_bytes(), 3)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let first = input.first().ok_or("no elves found")?;
+        Ok(first.total.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input.iter().map(|elf| elf.total).sum::<i32>().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_n_test() {
+        let mut top = TopN::new(3);
+        for value in [5, 1, 9, 3, 7, 9, 2] {
+            top.push(value);
+        }
+        assert_eq!(top.into_sorted(), vec![9, 9, 7]);
+
+        let mut none = TopN::new(0);
+        none.push(1);
+        assert_eq!(none.into_sorted(), vec![]);
+
+        // Nothing is allocated up front, so any limit can be asked for.
+        let mut all = TopN::new(usize::MAX);
+        all.push(1);
+        assert_eq!(all.into_sorted(), vec![1]);
+    }
+
+    #[test]
+    fn top_elves_test() {
+        // Leading, trailing and repeated blank lines don't create empty elves.
+        let data = "\n100\n200\n\n\n500\n\n50\n50\n\n300\n\n";
+        let elves = top_elves(data.as_bytes(), 2).unwrap();
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf {
+                    index: 1,
+                    total: 500
+                },
+                Elf {
+                    index: 0,
+                    total: 300
+                }
+            ]
+        );
+        assert_eq!(top_elves(data.as_bytes(), 10).unwrap().len(), 4);
+        assert!(top_elves("100\nlots\n".as_bytes(), 3).is_err());
+    }
+}
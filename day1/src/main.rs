@@ -1,63 +1,41 @@
+use std::env;
 use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 
-// Note: this was unecessary, I thought part 2 would use this data.
-struct Inventory {
-    total: i32,
-    calories: Vec<i32>,
-}
+use day1::top_elves;
 
+// Finds the elves carrying the most calories, the number of elves reported can be changed with
+// the --top argument:
+//   day1 <input file> [--top N]
 fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open("assets/input.txt").expect("couldn't open input file");
-
-    let mut inventory: Vec<Inventory> = vec![];
-    let mut first = 0;
-    let mut second = 0;
-    let mut third = 0;
-
-    for raw in BufReader::new(file).lines() {
-        if let Ok(line) = raw {
-            if line == "" || inventory.len() == 0 {
-                inventory.push(Inventory {
-                    total: 0,
-                    calories: vec![],
-                });
-
-                if line == "" {
-                    continue;
-                }
-            }
-
-            let calories = line.parse()?;
-
-            let last = inventory.last_mut().ok_or("empty list")?;
-
-            last.total += calories;
-            last.calories.push(calories);
-
-            if last.total > third {
-                if last.total > second {
-                    if last.total > first {
-                        third = second;
-                        second = first;
-                        first = last.total;
-                    } else {
-                        third = second;
-                        second = last.total;
-                    }
-                } else {
-                    third = last.total;
-                }
-            }
-        }
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let (path, top) = match args.as_slice() {
+        [path] => (path, 3),
+        [path, flag, count] if flag == "--top" => (path, count.parse()?),
+        _ => panic!(
+            "expected the path to the input file and an optional --top N, not {:?}",
+            args
+        ),
+    };
+
+    let file = File::open(path).expect("couldn't open input file");
+    let elves = top_elves(BufReader::new(file), top)?;
+
+    for (rank, elf) in elves.iter().enumerate() {
+        println!(
+            "{}. elf {} carries {} calories",
+            rank + 1,
+            elf.index,
+            elf.total
+        );
     }
-
     println!(
-        "highest calorie inventories: {} {} {}",
-        first, second, third
+        "total top {} calories: {}",
+        elves.len(),
+        elves.iter().map(|elf| elf.total).sum::<i32>()
     );
-    println!("total top 3 calories: {}", first + second + third);
 
     Ok(())
 }
@@ -0,0 +1,643 @@
+/// Simulates the game of Rock, Paper, Scissors (RPS) by parsing an input file that represents a
+/// gameplay strategy and calculating outcomes.
+/// Notes:
+///   - The game is a rule set of an odd number of shapes in a cycle, where each shape beats the
+///     shapes an odd number of places before it. With three shapes this is standard RPS: Rock
+///     beats Scissors, Paper beats Rock, and Scissors beats Paper.
+///   - The input letters are mapped to shapes and outcomes with a mapping table, the puzzle uses
+///     A/B/C for the opponent and X/Y/Z for column two.
+///   - The first part of the problem is generating the results from the input being column one as
+///     the opponent's shape and column two as the player's shape.
+///   - The second part of the problem is generating the results from from the input being column
+///     one as the opponent's shape and column two as the desired result, which adds calculating
+///     the required move.
+use std::collections::HashMap;
+use std::error::Error;
+
+use common::Solution;
+
+// The letter mapping used by the puzzle input.
+pub const CLASSIC_MAPPING: &str = "\
+opponent A=Rock B=Paper C=Scissors
+player X=Rock Y=Paper Z=Scissors
+outcome X=Loss Y=Draw Z=Win
+";
+
+// The shapes of a game, in cycle order.
+// Note: shape i beats shape j when (i - j) mod N is odd, which gives every shape the same number
+//   of wins and losses as long as N is odd. Shapes are worth their position plus one, so with the
+//   classic rules:
+//   Rock => 1
+//   Paper => 2
+//   Scissors => 3
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleSet {
+    names: Vec<String>,
+}
+
+impl RuleSet {
+    // A rule set of the named shapes in cycle order.
+    pub fn new(names: &[&str]) -> Result<Self, Box<dyn Error>> {
+        if names.len().is_multiple_of(2) {
+            return Err(format!(
+                "a rule set needs an odd number of shapes, not {}",
+                names.len()
+            )
+            .into());
+        }
+
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                return Err(format!("{} is in the rule set twice", name).into());
+            }
+        }
+
+        Ok(RuleSet {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        })
+    }
+
+    // Rock, Paper, Scissors.
+    pub fn classic() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    // Rock, Paper, Scissors, Lizard, Spock, with the cycle ordered so the odd rule matches the
+    // usual rules.
+    pub fn lizard_spock() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
+    }
+
+    // The number of shapes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    // Every shape in cycle order.
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        let count = self.len();
+        (0..count).map(move |index| Shape { index, count })
+    }
+
+    // Find a shape by name.
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        let index = self.names.iter().position(|shape| shape == name)?;
+        Some(Shape {
+            index,
+            count: self.len(),
+        })
+    }
+
+    // The name of a shape in this rule set.
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.index]
+    }
+}
+
+// A shape played during a game, the position of the shape in its rule set's cycle.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Shape {
+    index: usize,
+    count: usize,
+}
+
+impl Shape {
+    // The point value assigned to a shape.
+    pub fn value(&self) -> i32 {
+        self.index as i32 + 1
+    }
+
+    // Generate the results of a game against the opponent's played shape.
+    pub fn play(&self, opponent: &Shape) -> Outcome {
+        let distance = (self.index + self.count - opponent.index) % self.count;
+
+        if distance == 0 {
+            Outcome::Draw
+        } else if distance % 2 == 1 {
+            Outcome::Win
+        } else {
+            Outcome::Loss
+        }
+    }
+
+    // Generate the shape that the player needs to play to achieve the desired result.
+    // Note: The self shape is the opponent's move, but the desired result is from the perspevtive
+    //   of the generated move. The next shape in the cycle always wins and the previous one
+    //   always loses.
+    pub fn solve(&self, result: &Outcome) -> Self {
+        let index = match result {
+            Outcome::Loss => (self.index + self.count - 1) % self.count,
+            Outcome::Draw => self.index,
+            Outcome::Win => (self.index + 1) % self.count,
+        };
+
+        Shape {
+            index,
+            count: self.count,
+        }
+    }
+}
+
+// The game outcome and points awarded.
+// Note: the points awarded are:
+//   Loss => 0
+//   Draw => 3
+//   Win => 6
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    // The point value assigned to an outcome.
+    pub fn value(&self) -> i32 {
+        match self {
+            Self::Loss => 0,
+            Self::Draw => 3,
+            Self::Win => 6,
+        }
+    }
+
+    // The opposite of an outcome.
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::Loss => Self::Win,
+            Self::Draw => Self::Draw,
+            Self::Win => Self::Loss,
+        }
+    }
+
+    // Find an outcome by name.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "Loss" => Some(Self::Loss),
+            "Draw" => Some(Self::Draw),
+            "Win" => Some(Self::Win),
+            _ => None,
+        }
+    }
+}
+
+// How column two of the strategy guide is read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interpretation {
+    // Column two is the player's shape, part 1.
+    Shape,
+    // Column two is the outcome the player needs, part 2.
+    Outcome,
+}
+
+// The table mapping the input letters to shapes and outcomes.
+// Note: the table has one line per column, each listing letter=name pairs:
+//   opponent A=Rock B=Paper C=Scissors
+//   player X=Rock Y=Paper Z=Scissors
+//   outcome X=Loss Y=Draw Z=Win
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapping {
+    opponent: HashMap<String, Shape>,
+    player: HashMap<String, Shape>,
+    outcome: HashMap<String, Outcome>,
+}
+
+impl Mapping {
+    // Parse a mapping table for the shapes of the rule set.
+    pub fn parse(rules: &RuleSet, table: &str) -> Result<Self, Box<dyn Error>> {
+        let mut mapping = Mapping {
+            opponent: HashMap::new(),
+            player: HashMap::new(),
+            outcome: HashMap::new(),
+        };
+
+        for (number, line) in table.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let column = match tokens.next() {
+                Some(column) => column,
+                None => continue,
+            };
+
+            for pair in tokens {
+                let (letter, name) = pair.split_once('=').ok_or_else(|| {
+                    format!("mapping line {}: {} isn't letter=name", number + 1, pair)
+                })?;
+                let unknown =
+                    || format!("mapping line {}: unknown {} {}", number + 1, column, name);
+
+                match column {
+                    "opponent" => {
+                        let shape = rules.shape(name).ok_or_else(unknown)?;
+                        mapping.opponent.insert(letter.to_string(), shape);
+                    }
+                    "player" => {
+                        let shape = rules.shape(name).ok_or_else(unknown)?;
+                        mapping.player.insert(letter.to_string(), shape);
+                    }
+                    "outcome" => {
+                        let outcome = Outcome::named(name).ok_or_else(unknown)?;
+                        mapping.outcome.insert(letter.to_string(), outcome);
+                    }
+                    _ => {
+                        return Err(format!(
+                            "mapping line {}: unknown column {}",
+                            number + 1,
+                            column
+                        )
+                        .into())
+                    }
+                }
+            }
+        }
+
+        Ok(mapping)
+    }
+
+    // The puzzle's mapping for the classic rules.
+    pub fn classic() -> Self {
+        Self::parse(&RuleSet::classic(), CLASSIC_MAPPING).unwrap()
+    }
+
+    // The opponent's shape for a column one letter.
+    pub fn opponent(&self, letter: &str) -> Option<Shape> {
+        self.opponent.get(letter).copied()
+    }
+
+    // The player's shape for a column two letter, either the shape itself or the shape needed
+    // for the outcome against the opponent.
+    pub fn player(
+        &self,
+        letter: &str,
+        opponent: Shape,
+        interpretation: Interpretation,
+    ) -> Option<Shape> {
+        match interpretation {
+            Interpretation::Shape => self.player.get(letter).copied(),
+            Interpretation::Outcome => self
+                .outcome
+                .get(letter)
+                .map(|outcome| opponent.solve(outcome)),
+        }
+    }
+
+    // The column two letters of the interpretation, sorted.
+    pub fn letters(&self, interpretation: Interpretation) -> Vec<&str> {
+        let mut letters: Vec<&str> = match interpretation {
+            Interpretation::Shape => self.player.keys().map(String::as_str).collect(),
+            Interpretation::Outcome => self.outcome.keys().map(String::as_str).collect(),
+        };
+        letters.sort_unstable();
+
+        letters
+    }
+}
+
+// A single line of the strategy guide, the letters in each column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    pub opponent: String,
+    pub column_two: String,
+}
+
+// Parse the strategy guide, one round per line.
+pub fn parse(data: &str) -> Result<Vec<Line>, Box<dyn Error>> {
+    let mut guide = vec![];
+
+    for line in data.lines() {
+        let moves: Vec<&str> = line.split(' ').collect();
+        if moves.len() != 2 {
+            return Err(format!("input line {:?} is invalid", line).into());
+        }
+
+        guide.push(Line {
+            opponent: moves[0].to_string(),
+            column_two: moves[1].to_string(),
+        });
+    }
+
+    Ok(guide)
+}
+
+// The final score of a game.
+#[derive(Debug, Default, PartialEq)]
+pub struct Score {
+    pub rounds: usize,
+    pub player: i32,
+    pub opponent: i32,
+}
+
+impl Score {
+    // Score a round, both players score their shape plus the outcome of the round from their side.
+    pub fn add(&mut self, opponent: Shape, player: Shape) {
+        let result = player.play(&opponent);
+        self.rounds += 1;
+        self.player += player.value() + result.value();
+        self.opponent += opponent.value() + result.reverse().value();
+    }
+}
+
+// The opponent's and player's shape in every round of the strategy guide, reading column two
+// with the interpretation.
+pub fn moves(
+    guide: &[Line],
+    mapping: &Mapping,
+    interpretation: Interpretation,
+) -> Result<Vec<(Shape, Shape)>, Box<dyn Error>> {
+    let mut moves = vec![];
+
+    for (number, line) in guide.iter().enumerate() {
+        let invalid = |letter: &str| format!("line {}: {} is an invalid move", number + 1, letter);
+
+        let opponent = mapping
+            .opponent(&line.opponent)
+            .ok_or_else(|| invalid(&line.opponent))?;
+        let player = mapping
+            .player(&line.column_two, opponent, interpretation)
+            .ok_or_else(|| invalid(&line.column_two))?;
+
+        moves.push((opponent, player));
+    }
+
+    Ok(moves)
+}
+
+// Play every round of the strategy guide, reading column two with the interpretation.
+pub fn simulate(
+    guide: &[Line],
+    mapping: &Mapping,
+    interpretation: Interpretation,
+) -> Result<Score, Box<dyn Error>> {
+    let mut score = Score::default();
+    for (opponent, player) in moves(guide, mapping, interpretation)? {
+        score.add(opponent, player);
+    }
+
+    Ok(score)
+}
+
+// Whether a strategy should get the highest or the lowest score.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Goal {
+    Highest,
+    Lowest,
+}
+
+// The column two letters chosen for every round and the score they get.
+#[derive(Debug, PartialEq)]
+pub struct Strategy {
+    pub letters: Vec<String>,
+    pub score: Score,
+}
+
+// Find the column two letters that get the highest or lowest player score against the opponent's
+// moves, reading column two with the interpretation.
+// Note: rounds don't affect each other, so the best letter is chosen for each round on its own.
+//   Ties go to the first letter alphabetically.
+pub fn optimize(
+    opponents: &[String],
+    mapping: &Mapping,
+    interpretation: Interpretation,
+    goal: Goal,
+) -> Result<Strategy, Box<dyn Error>> {
+    let letters = mapping.letters(interpretation);
+    let mut strategy = Strategy {
+        letters: vec![],
+        score: Score::default(),
+    };
+
+    for (number, letter) in opponents.iter().enumerate() {
+        let opponent = mapping
+            .opponent(letter)
+            .ok_or_else(|| format!("line {}: {} is an invalid move", number + 1, letter))?;
+
+        let mut best: Option<(&str, Shape, i32)> = None;
+        for letter in &letters {
+            let player = mapping.player(letter, opponent, interpretation).unwrap();
+            let points = player.value() + player.play(&opponent).value();
+
+            let better = match (best, goal) {
+                (None, _) => true,
+                (Some((_, _, best)), Goal::Highest) => points > best,
+                (Some((_, _, best)), Goal::Lowest) => points < best,
+            };
+            if better {
+                best = Some((letter, player, points));
+            }
+        }
+
+        let (letter, player, _) = best.ok_or("the mapping has no column two letters")?;
+        strategy.letters.push(letter.to_string());
+        strategy.score.add(opponent, player);
+    }
+
+    Ok(strategy)
+}
+
+// Parse only the opponent's column of a strategy guide.
+pub fn parse_opponents(data: &str) -> Vec<String> {
+    data.lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+// The results of a single player over a tournament.
+#[derive(Debug, Default, PartialEq)]
+pub struct Standing {
+    pub score: i32,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+// Play a round robin tournament where every player plays every other player once. A player's
+// shapes are played in order, one per round, and each round scores the shape plus the outcome.
+// Note: a game lasts as long as the shorter of the two players' shape lists.
+pub fn tournament(players: &[Vec<Shape>]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players.iter().map(|_| Standing::default()).collect();
+
+    for first in 0..players.len() {
+        for second in first + 1..players.len() {
+            for (one, two) in players[first].iter().zip(&players[second]) {
+                let result = one.play(two);
+
+                for (index, shape, outcome) in
+                    [(first, one, result), (second, two, result.reverse())]
+                {
+                    let standing = &mut standings[index];
+                    standing.score += shape.value() + outcome.value();
+                    match outcome {
+                        Outcome::Win => standing.wins += 1,
+                        Outcome::Draw => standing.draws += 1,
+                        Outcome::Loss => standing.losses += 1,
+                    }
+                }
+            }
+        }
+    }
+
+    standings
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Line>;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let score = simulate(input, &Mapping::classic(), Interpretation::Shape)?;
+        Ok(score.player.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        let score = simulate(input, &Mapping::classic(), Interpretation::Outcome)?;
+        Ok(score.player.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_test() {
+        let rules = RuleSet::classic();
+        let rock = rules.shape("Rock").unwrap();
+        let paper = rules.shape("Paper").unwrap();
+        let scissors = rules.shape("Scissors").unwrap();
+
+        assert_eq!(rock.play(&scissors), Outcome::Win);
+        assert_eq!(paper.play(&rock), Outcome::Win);
+        assert_eq!(scissors.play(&paper), Outcome::Win);
+        assert_eq!(rock.play(&paper), Outcome::Loss);
+        assert_eq!(rock.play(&rock), Outcome::Draw);
+        assert_eq!(rock.solve(&Outcome::Loss), scissors);
+        assert_eq!(scissors.value(), 3);
+    }
+
+    #[test]
+    fn lizard_spock_test() {
+        let rules = RuleSet::lizard_spock();
+        let beats = [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ];
+
+        for (winner, loser) in beats {
+            let winner = rules.shape(winner).unwrap();
+            let loser = rules.shape(loser).unwrap();
+            assert_eq!(winner.play(&loser), Outcome::Win);
+            assert_eq!(loser.play(&winner), Outcome::Loss);
+        }
+
+        for shape in rules.shapes() {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                assert_eq!(shape.solve(&outcome).play(&shape), outcome);
+            }
+        }
+
+        assert!(RuleSet::new(&["Rock", "Paper"]).is_err());
+        assert!(RuleSet::new(&["Rock", "Rock", "Paper"]).is_err());
+    }
+
+    #[test]
+    fn mapping_test() {
+        let rules = RuleSet::lizard_spock();
+        let mapping = Mapping::parse(
+            &rules,
+            "opponent A=Rock B=Spock\nplayer X=Lizard Y=Paper\noutcome X=Win Y=Draw\n",
+        )
+        .unwrap();
+        let guide = parse("A X\nB Y").unwrap();
+
+        // Lizard loses to Rock (0 + 5), Paper beats Spock (6 + 2).
+        let score = simulate(&guide, &mapping, Interpretation::Shape).unwrap();
+        assert_eq!((score.player, score.opponent), (13, 11));
+
+        // Paper beats Rock (6 + 2), Spock draws (3 + 4).
+        let score = simulate(&guide, &mapping, Interpretation::Outcome).unwrap();
+        assert_eq!((score.player, score.opponent), (15, 8));
+
+        assert!(simulate(&parse("C X").unwrap(), &mapping, Interpretation::Shape).is_err());
+        assert!(Mapping::parse(&rules, "opponent A=Stone").is_err());
+    }
+
+    #[test]
+    fn optimize_test() {
+        let mapping = Mapping::classic();
+        let opponents = parse_opponents("A Y\nB X\nC Z");
+
+        // Beating Rock with Paper, Paper with Scissors and Scissors with Rock.
+        let highest = optimize(&opponents, &mapping, Interpretation::Shape, Goal::Highest).unwrap();
+        assert_eq!(highest.letters, vec!["Y", "Z", "X"]);
+        assert_eq!(highest.score.player, 8 + 9 + 7);
+
+        // Losing scores less than a draw even with the cheapest shapes.
+        let lowest = optimize(&opponents, &mapping, Interpretation::Shape, Goal::Lowest).unwrap();
+        assert_eq!(lowest.letters, vec!["Z", "X", "Y"]);
+        assert_eq!(lowest.score.player, 3 + 1 + 2);
+
+        let highest =
+            optimize(&opponents, &mapping, Interpretation::Outcome, Goal::Highest).unwrap();
+        assert_eq!(highest.letters, vec!["Z", "Z", "Z"]);
+        assert_eq!(
+            highest.score,
+            simulate(
+                &parse("A Z\nB Z\nC Z").unwrap(),
+                &mapping,
+                Interpretation::Outcome
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn tournament_test() {
+        let rules = RuleSet::classic();
+        let rock = rules.shape("Rock").unwrap();
+        let paper = rules.shape("Paper").unwrap();
+        let scissors = rules.shape("Scissors").unwrap();
+
+        let standings = tournament(&[vec![rock, rock], vec![paper, rock], vec![scissors]]);
+
+        // Rock and paper play two rounds, everyone plays scissors once.
+        assert_eq!(
+            standings,
+            vec![
+                Standing {
+                    score: 1 + 4 + 7,
+                    wins: 1,
+                    draws: 1,
+                    losses: 1
+                },
+                Standing {
+                    score: 8 + 4 + 2,
+                    wins: 1,
+                    draws: 1,
+                    losses: 1
+                },
+                Standing {
+                    score: 9 + 3,
+                    wins: 1,
+                    draws: 0,
+                    losses: 1
+                },
+            ]
+        );
+    }
+}
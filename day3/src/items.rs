@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::{BitAnd, BitOr};
+
+// The items of the puzzle in priority order.
+pub const CLASSIC_ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// The priority of every item, items are numbered from 1 in the order they are listed.
+// Note: the puzzle's priorities are:
+//   a-z => 1 - 26
+//   A-Z => 27 - 52
+//   but any characters can be listed, so the table isn't limited to ASCII.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriorityTable {
+    priorities: HashMap<char, u32>,
+    items: Vec<char>,
+}
+
+impl PriorityTable {
+    // A table of the items listed in priority order, starting from 1.
+    pub fn new(items: &str) -> Result<Self, Box<dyn Error>> {
+        let mut table = PriorityTable {
+            priorities: HashMap::new(),
+            items: vec![],
+        };
+
+        for item in items.chars() {
+            if table.priorities.contains_key(&item) {
+                return Err(format!("{} is in the priority table twice", item).into());
+            }
+
+            table.items.push(item);
+            table.priorities.insert(item, table.items.len() as u32);
+        }
+
+        Ok(table)
+    }
+
+    // The puzzle's a-z then A-Z table.
+    pub fn classic() -> Self {
+        Self::new(CLASSIC_ITEMS).unwrap()
+    }
+
+    // The number of items in the table, which is also the highest priority.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // The priority of an item, or None if it isn't in the table.
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.priorities.get(&item).copied()
+    }
+
+    // The item with a priority.
+    pub fn item(&self, priority: u32) -> char {
+        self.items[priority as usize - 1]
+    }
+}
+
+// A set of items stored as a bitset, where each item is the bit of its priority. Intersections
+// and unions are a single AND or OR.
+// Note: bit 0 is never used, so a set holds one item less than its number of bits.
+pub trait ItemSet: Copy + Eq + BitAnd<Output = Self> + BitOr<Output = Self> {
+    // The number of bits in the set.
+    const BITS: u32;
+
+    // The set with no items.
+    fn empty() -> Self;
+
+    // The set with the item of the priority added.
+    fn with(self, priority: u32) -> Self;
+
+    // The priorities of the items in the set, lowest first.
+    fn priorities(self) -> Vec<u32>;
+
+    // The set with every item of another set added.
+    fn union(self, other: Self) -> Self {
+        self | other
+    }
+
+    // The set of items in both sets.
+    fn intersection(self, other: Self) -> Self {
+        self & other
+    }
+}
+
+macro_rules! item_set {
+    ($type:ty) => {
+        impl ItemSet for $type {
+            const BITS: u32 = <$type>::BITS;
+
+            fn empty() -> Self {
+                0
+            }
+
+            fn with(self, priority: u32) -> Self {
+                self | (1 << priority)
+            }
+
+            fn priorities(self) -> Vec<u32> {
+                let mut priorities = vec![];
+                let mut bits = self;
+
+                while bits != 0 {
+                    priorities.push(bits.trailing_zeros());
+                    bits &= bits - 1;
+                }
+
+                priorities
+            }
+        }
+    };
+}
+
+item_set!(u64);
+item_set!(u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_test() {
+        let table = PriorityTable::classic();
+        assert_eq!(table.priority('p'), Some(16));
+        assert_eq!(table.priority('L'), Some(38));
+        assert_eq!(table.priority('é'), None);
+        assert_eq!(table.item(52), 'Z');
+
+        let table = PriorityTable::new("äöü").unwrap();
+        assert_eq!(table.priority('ü'), Some(3));
+        assert!(PriorityTable::new("abca").is_err());
+    }
+
+    #[test]
+    fn item_set_test() {
+        let first = u128::empty().with(1).with(100).with(127);
+        let second = u128::empty().with(100).with(127).with(5);
+
+        assert_eq!(first.intersection(second).priorities(), vec![100, 127]);
+        assert_eq!(first.union(second).priorities(), vec![1, 5, 100, 127]);
+        assert_eq!(u64::empty().with(63).priorities(), vec![63]);
+    }
+}
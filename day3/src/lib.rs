@@ -0,0 +1,421 @@
+/// Checks the elves rucksacks for bad items and group badges by parsing an input file that
+/// represents each rucksack.
+/// Notes:
+///   - Each line represents the two equal sized compartments of a single rucksack.
+///   - Each rucksack has only one bad item which is exists in both compartments.
+///   - The badges are determined by the common item found in every group of rucksacks, the
+///     puzzle uses groups of three.
+///   - Rucksacks are stored as bitsets of item priorities, so finding the shared items is a
+///     bitwise AND of the compartments or the group.
+use std::error::Error;
+use std::fmt;
+
+use common::Solution;
+
+pub mod items;
+
+use crate::items::*;
+
+// The group size used by the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+// The items in each compartment of a rucksack, and the input line it came from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rucksack<S: ItemSet> {
+    pub line: usize,
+    pub first: S,
+    pub second: S,
+}
+
+impl<S: ItemSet> Rucksack<S> {
+    // Every item in the rucksack.
+    pub fn items(&self) -> S {
+        self.first.union(self.second)
+    }
+}
+
+// What is wrong with a rucksack or group.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    // The item isn't in the priority table.
+    UnknownItem(char),
+    // The priority table has more items than fit in the bitset.
+    TableTooLarge(usize),
+    // The compartments don't share an item.
+    NoSharedItem,
+    // The compartments share more than one item.
+    MultipleSharedItems(Vec<char>),
+    // The rucksacks of the group don't share an item.
+    NoBadge,
+    // The rucksacks of the group share more than one item.
+    MultipleBadges(Vec<char>),
+    // The line has an odd number of items, so the compartments can't be the same size.
+    OddLength(usize),
+    // The last group only has this many rucksacks.
+    IncompleteGroup(usize),
+    // The group size is zero, so there are no groups to find badges in.
+    EmptyGroup,
+}
+
+// A problem found on an input line, group problems are reported on the group's first line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuckError {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for RuckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.problem {
+            Problem::UnknownItem(item) => write!(f, "{:?} is not a valid item", item),
+            Problem::TableTooLarge(len) => write!(f, "{} items don't fit in the rucksack", len),
+            Problem::NoSharedItem => write!(f, "the compartments don't share an item"),
+            Problem::MultipleSharedItems(items) => {
+                write!(f, "the compartments share {:?}", items)
+            }
+            Problem::NoBadge => write!(f, "the group doesn't share an item"),
+            Problem::MultipleBadges(items) => write!(f, "the group shares {:?}", items),
+            Problem::OddLength(len) => write!(f, "{} items can't be split in half", len),
+            Problem::IncompleteGroup(len) => {
+                write!(f, "the last group only has {} rucksacks", len)
+            }
+            Problem::EmptyGroup => write!(f, "the group size must be above zero"),
+        }
+    }
+}
+
+impl Error for RuckError {}
+
+// The results of checking every rucksack.
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub rucksacks: usize,
+    pub bad_value: u32,
+    pub badge_value: u32,
+}
+
+// Parse each line into a rucksack, the first half of the line is the first compartment.
+pub fn parse<S: ItemSet>(data: &str, table: &PriorityTable) -> Result<Vec<Rucksack<S>>, RuckError> {
+    // Bit 0 isn't used, so the highest priority has to be below the number of bits.
+    if table.len() >= S::BITS as usize {
+        return Err(RuckError {
+            line: 1,
+            problem: Problem::TableTooLarge(table.len()),
+        });
+    }
+
+    data.lines()
+        .enumerate()
+        .map(|(index, raw)| parse_line(index + 1, raw, table))
+        .collect()
+}
+
+// Parse a single line into a rucksack.
+fn parse_line<S: ItemSet>(
+    line: usize,
+    raw: &str,
+    table: &PriorityTable,
+) -> Result<Rucksack<S>, RuckError> {
+    let items: Vec<char> = raw.chars().collect();
+    let (first, second) = items.split_at(items.len() / 2);
+
+    let compartment = |items: &[char]| {
+        items.iter().try_fold(S::empty(), |set, item| {
+            let priority = table.priority(*item).ok_or(RuckError {
+                line,
+                problem: Problem::UnknownItem(*item),
+            })?;
+            Ok(set.with(priority))
+        })
+    };
+
+    Ok(Rucksack {
+        line,
+        first: compartment(first)?,
+        second: compartment(second)?,
+    })
+}
+
+// The single item in a set, or the problem if there are none or several.
+fn single<S: ItemSet>(
+    set: S,
+    table: &PriorityTable,
+    none: Problem,
+    several: fn(Vec<char>) -> Problem,
+) -> Result<u32, Problem> {
+    match set.priorities().as_slice() {
+        [] => Err(none),
+        [priority] => Ok(*priority),
+        priorities => Err(several(
+            priorities
+                .iter()
+                .map(|priority| table.item(*priority))
+                .collect(),
+        )),
+    }
+}
+
+// The priority of the item found in both compartments of the rucksack.
+pub fn bad_item<S: ItemSet>(
+    rucksack: &Rucksack<S>,
+    table: &PriorityTable,
+) -> Result<u32, RuckError> {
+    let shared = rucksack.first.intersection(rucksack.second);
+
+    single(
+        shared,
+        table,
+        Problem::NoSharedItem,
+        Problem::MultipleSharedItems,
+    )
+    .map_err(|problem| RuckError {
+        line: rucksack.line,
+        problem,
+    })
+}
+
+// The priority of the badge item found in every rucksack of the group.
+pub fn group_badge<S: ItemSet>(
+    group: &[Rucksack<S>],
+    table: &PriorityTable,
+) -> Result<u32, RuckError> {
+    let shared = group
+        .iter()
+        .map(Rucksack::items)
+        .reduce(S::intersection)
+        .unwrap_or_else(S::empty);
+
+    single(shared, table, Problem::NoBadge, Problem::MultipleBadges).map_err(|problem| RuckError {
+        line: group.first().map_or(0, |rucksack| rucksack.line),
+        problem,
+    })
+}
+
+// The total priority of the bad items in every rucksack.
+pub fn bad_value<S: ItemSet>(
+    rucksacks: &[Rucksack<S>],
+    table: &PriorityTable,
+) -> Result<u32, RuckError> {
+    rucksacks
+        .iter()
+        .map(|rucksack| bad_item(rucksack, table))
+        .sum()
+}
+
+// The total priority of the badges of every group of rucksacks.
+// Note: a group size of zero is an error, and any rucksacks left over after the last full group
+//   are ignored.
+pub fn badge_value<S: ItemSet>(
+    rucksacks: &[Rucksack<S>],
+    group_size: usize,
+    table: &PriorityTable,
+) -> Result<u32, RuckError> {
+    if group_size == 0 {
+        return Err(empty_group());
+    }
+
+    rucksacks
+        .chunks_exact(group_size)
+        .map(|group| group_badge(group, table))
+        .sum()
+}
+
+// The error for a group size of zero, reported on the first line like the other setup problems.
+fn empty_group() -> RuckError {
+    RuckError {
+        line: 1,
+        problem: Problem::EmptyGroup,
+    }
+}
+
+// Check the rucksacks for bad items and the badges of each group.
+pub fn ruck_check<S: ItemSet>(
+    data: &str,
+    table: &PriorityTable,
+    group_size: usize,
+) -> Result<Report, Box<dyn Error>> {
+    if group_size == 0 {
+        return Err(empty_group().into());
+    }
+
+    let rucksacks: Vec<Rucksack<S>> = parse(data, table)?;
+
+    Ok(Report {
+        rucksacks: rucksacks.len(),
+        bad_value: bad_value(&rucksacks, table)?,
+        badge_value: badge_value(&rucksacks, group_size, table)?,
+    })
+}
+
+// Check every rucksack and group, listing every problem found in line order instead of stopping
+// at the first one. Groups with a rucksack that couldn't be parsed aren't checked for a badge.
+pub fn validate<S: ItemSet>(
+    data: &str,
+    table: &PriorityTable,
+    group_size: usize,
+) -> Vec<RuckError> {
+    if table.len() >= S::BITS as usize {
+        return vec![RuckError {
+            line: 1,
+            problem: Problem::TableTooLarge(table.len()),
+        }];
+    }
+
+    if group_size == 0 {
+        return vec![empty_group()];
+    }
+
+    let mut errors = vec![];
+    let mut rucksacks = vec![];
+
+    for (index, raw) in data.lines().enumerate() {
+        let line = index + 1;
+
+        let len = raw.chars().count();
+        if len % 2 == 1 {
+            errors.push(RuckError {
+                line,
+                problem: Problem::OddLength(len),
+            });
+        }
+
+        match parse_line::<S>(line, raw, table) {
+            Ok(rucksack) => {
+                errors.extend(bad_item(&rucksack, table).err());
+                rucksacks.push(Some(rucksack));
+            }
+            Err(error) => {
+                errors.push(error);
+                rucksacks.push(None);
+            }
+        }
+    }
+
+    let groups = rucksacks.chunks_exact(group_size);
+    if !groups.remainder().is_empty() {
+        errors.push(RuckError {
+            line: rucksacks.len() - groups.remainder().len() + 1,
+            problem: Problem::IncompleteGroup(groups.remainder().len()),
+        });
+    }
+
+    for group in groups {
+        if let Some(group) = group.iter().copied().collect::<Option<Vec<Rucksack<S>>>>() {
+            errors.extend(group_badge(&group, table).err());
+        }
+    }
+
+    errors.sort_by_key(|error| error.line);
+
+    errors
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack<u64>>;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(data, &PriorityTable::classic())?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(bad_value(input, &PriorityTable::classic())?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(badge_value(input, GROUP_SIZE, &PriorityTable::classic())?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+        PmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+        ttgJtRGJQctTZtZT\n\
+        CrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn group_size_test() {
+        let table = PriorityTable::classic();
+        let rucksacks: Vec<Rucksack<u128>> = parse(EXAMPLE, &table).unwrap();
+
+        assert_eq!(bad_value(&rucksacks, &table), Ok(157));
+        assert_eq!(badge_value(&rucksacks, 3, &table), Ok(70));
+        // Every rucksack on its own is a group, so each badge is ambiguous.
+        assert!(badge_value(&rucksacks, 1, &table).is_err());
+        assert_eq!(
+            badge_value(&rucksacks, 6, &table).unwrap_err().problem,
+            Problem::NoBadge
+        );
+        assert_eq!(badge_value(&rucksacks, 0, &table), Err(empty_group()));
+    }
+
+    #[test]
+    fn errors_test() {
+        let table = PriorityTable::classic();
+
+        let rucksacks: Vec<Rucksack<u64>> = parse("abcabd\nabcdef", &table).unwrap();
+        assert_eq!(
+            bad_item(&rucksacks[0], &table).unwrap_err(),
+            RuckError {
+                line: 1,
+                problem: Problem::MultipleSharedItems(vec!['a', 'b']),
+            }
+        );
+        assert_eq!(
+            bad_item(&rucksacks[1], &table).unwrap_err().problem,
+            Problem::NoSharedItem
+        );
+
+        let error = parse::<u64>("ab\naé", &table).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: 'é' is not a valid item");
+
+        // Unicode items work with a custom table.
+        let table = PriorityTable::new("äöüß").unwrap();
+        let rucksacks: Vec<Rucksack<u64>> = parse("äöüä", &table).unwrap();
+        assert_eq!(bad_item(&rucksacks[0], &table), Ok(1));
+    }
+
+    #[test]
+    fn validate_test() {
+        let table = PriorityTable::classic();
+        let data = "abcaXd\nabcdef\nabcabc\nxyzxa\nAé\nAAA";
+        let problems: Vec<(usize, Problem)> = validate::<u64>(data, &table, 2)
+            .into_iter()
+            .map(|error| (error.line, error.problem))
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                (1, Problem::MultipleBadges(vec!['a', 'b', 'c', 'd'])),
+                (2, Problem::NoSharedItem),
+                (3, Problem::MultipleSharedItems(vec!['a', 'b', 'c'])),
+                (4, Problem::OddLength(5)),
+                (5, Problem::UnknownItem('é')),
+                (6, Problem::OddLength(3)),
+            ]
+        );
+
+        // The last two rucksacks don't make a group of four.
+        assert!(validate::<u64>(data, &table, 4).contains(&RuckError {
+            line: 5,
+            problem: Problem::IncompleteGroup(2),
+        }));
+
+        // Both modes reject a group size of zero the same way.
+        let empty = validate::<u64>(data, &table, 0);
+        assert_eq!(empty, vec![empty_group()]);
+        assert_eq!(
+            ruck_check::<u64>(data, &table, 0).unwrap_err().to_string(),
+            empty[0].to_string()
+        );
+    }
+}
@@ -1,127 +1,76 @@
-/// Checks the elves rucksacks for bad items and group badges by parsing an input file that
-/// represents each rucksack.
-/// Notes:
-///   - Each line represents the two equal sized compartments of a single rucksack.
-///   - Each rucksack has only one bad item which is exists in both compartments.
-///   - The badges are determined by the common item found in every three rucksacks.
-use std::collections::HashMap;
 use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-// Check the rucksacks for bad items.
-fn ruck_check(path: &str) -> Result<(), Box<dyn Error>> {
-    let mut bad_value = 0;
-    let mut rucks = 0;
-    let mut group: Vec<Vec<char>> = vec![];
-    let mut badge_value = 0;
-
-    for raw in BufReader::new(File::open(path)?).lines() {
-        let line: Vec<char> = raw?.chars().collect();
-
-        rucks += 1;
-        bad_value += item_value(bad_item(&line));
-
-        if group.len() < 3 {
-            group.push(line);
-
-            if group.len() == 3 {
-                badge_value += item_value(group_badge(group.clone()));
-                group.clear();
+use std::fs;
+use std::process;
+
+use day3::items::*;
+use day3::*;
+
+// Checks the rucksacks, the items, group size and bitset size can be changed with optional
+// arguments:
+//   day3 <input file> [--items <items in priority order>] [--group N] [--wide]
+//   --wide stores rucksacks in 128 bit sets, for tables of up to 127 items.
+// With --validate every problem with the rucksacks is listed instead, exiting with an error if
+// there are any.
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut path = None;
+    let mut items = CLASSIC_ITEMS.to_string();
+    let mut group_size = GROUP_SIZE;
+    let mut wide = false;
+    let mut validating = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--items" => items = args.next().expect("--items needs the items"),
+            "--group" => {
+                let size = args.next().expect("--group needs a size");
+                group_size = size.parse().expect("invalid group size");
             }
+            "--wide" => wide = true,
+            "--validate" => validating = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => panic!(
+                "expected only one argument: the path to the input file\n\tRecieved {:?}",
+                arg
+            ),
         }
     }
 
-    println!(
-        "Checking {} rucksacks resulted in {} worth of bad items.",
-        rucks, bad_value
-    );
+    let path = path.expect("expected the path to the input file");
+    let table = PriorityTable::new(&items).expect("invalid priority table");
+    let data = fs::read_to_string(path).expect("couldn't read input file");
 
-    println!("Badge group value {}", badge_value);
-
-    Ok(())
-}
-
-// Find the duplicate item in the rucksacks.
-// Note: each rucksack is the same length of characters.
-fn bad_item(rucksacks: &Vec<char>) -> char {
-    let middle = rucksacks.len() / 2;
-    let mut letters: HashMap<char, bool> = HashMap::new();
-
-    for i in 0..rucksacks.len() {
-        if i < middle {
-            letters.insert(rucksacks[i], true);
+    if validating {
+        let errors = if wide {
+            validate::<u128>(&data, &table, group_size)
         } else {
-            if letters.contains_key(&rucksacks[i]) {
-                return rucksacks[i];
-            }
-        }
-    }
-
-    panic!("couldn't find a bad item in {:?}", rucksacks);
-}
-
-// Determines the group badge for a given set of rucksacks.
-// Note: a group badge is the item that is common to all rucksacks.
-fn group_badge(ruck_group: Vec<Vec<char>>) -> char {
-    let mut seen: HashMap<char, i32> = HashMap::new();
+            validate::<u64>(&data, &table, group_size)
+        };
 
-    for item in ruck_group[0].iter() {
-        seen.insert(*item, 1);
-    }
-
-    for item in ruck_group[1].iter() {
-        let entry = seen.entry(*item).or_insert(0);
-        if *entry == 1 {
-            *entry = 2;
+        for error in &errors {
+            println!("{}", error);
         }
-    }
 
-    for item in ruck_group[2].iter() {
-        let entry = seen.entry(*item).or_insert(0);
-        if *entry == 2 {
-            *entry = 3;
+        if errors.is_empty() {
+            println!("no problems found");
+            return;
         }
-    }
 
-    for (key, value) in seen.iter() {
-        if *value == 3 {
-            return *key;
-        }
+        println!("{} problems found", errors.len());
+        process::exit(1);
     }
 
-    panic!("could not find a badge item: {:?}", seen);
-}
-
-// The value assigned to each item.
-// Note: the values are ascending alphabetically:
-//   a-z => 1 - 26
-//   A-Z => 27 - 52
-// BUG: this approach only works for ASCII encoded characters.
-fn item_value(item: char) -> i32 {
-    let mut value = item as i32;
-
-    if value >= 97 && value <= 122 {
-        value -= 96;
-    } else if value >= 65 && value <= 90 {
-        value -= 38;
+    let report = if wide {
+        ruck_check::<u128>(&data, &table, group_size)
     } else {
-        panic!("{} is not a valid item", item);
+        ruck_check::<u64>(&data, &table, group_size)
     }
+    .expect("failed while processing input");
 
-    value
-}
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!(
-            "expected only one argument: the path to the input file\n\tRecieved {:?}",
-            &args[1..]
-        );
-    }
-    let path = args[1].to_owned();
+    println!(
+        "Checking {} rucksacks resulted in {} worth of bad items.",
+        report.rucksacks, report.bad_value
+    );
 
-    ruck_check(&path).expect("failed while processing input");
+    println!("Badge group value {}", report.badge_value);
 }
@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// An inclusive range of sections, written "start-end".
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Interval {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Interval {
+    // The interval from start to end, or None if the end is before the start.
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        if start <= end {
+            Some(Interval { start, end })
+        } else {
+            None
+        }
+    }
+
+    // The number of sections in the interval.
+    // Note: there is no is_empty since an interval always has at least one section.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+
+    // Whether every section of the other interval is in this one.
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    // Whether the intervals share at least one section.
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    // The sections in both intervals, or None if they don't overlap.
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    // The sections in either interval, or None if there is a gap between them so they can't be
+    // joined into one interval.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        if self.touches(other) {
+            Interval::new(self.start.min(other.start), self.end.max(other.end))
+        } else {
+            None
+        }
+    }
+
+    // Whether the intervals overlap or are next to each other.
+    fn touches(&self, other: &Interval) -> bool {
+        self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Box<dyn Error>;
+
+    fn from_str(span: &str) -> Result<Self, Self::Err> {
+        let (start, end) = span
+            .split_once('-')
+            .ok_or_else(|| format!("{:?} isn't a start-end span", span))?;
+
+        Interval::new(start.trim().parse()?, end.trim().parse()?)
+            .ok_or_else(|| format!("{:?} ends before it starts", span).into())
+    }
+}
+
+// A set of sections stored as sorted intervals, where overlapping and neighboring intervals are
+// merged as they're inserted.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add the sections of the interval to the set.
+    pub fn insert(&mut self, interval: Interval) {
+        // Every interval before the first one that ends at or after the new start minus one can't
+        // touch it.
+        let first = self
+            .intervals
+            .partition_point(|existing| existing.end.saturating_add(1) < interval.start);
+
+        let mut merged = interval;
+        let mut last = first;
+        while last < self.intervals.len() {
+            match merged.union(&self.intervals[last]) {
+                Some(union) => merged = union,
+                None => break,
+            }
+            last += 1;
+        }
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    // The merged intervals, in order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    // The number of sections in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // Whether the section is in the set.
+    pub fn contains(&self, section: u32) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end < section);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.start <= section)
+    }
+
+    // The sections between the first and last section of the set that aren't in it.
+    pub fn gaps(&self) -> Vec<Interval> {
+        self.intervals
+            .windows(2)
+            .filter_map(|pair| Interval::new(pair[0].end + 1, pair[1].start - 1))
+            .collect()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn interval_test() {
+        let wide = span(2, 8);
+        let narrow = span(3, 7);
+        let apart = span(10, 12);
+
+        assert!(wide.contains(&narrow));
+        assert!(!narrow.contains(&wide));
+        assert!(wide.overlaps(&narrow));
+        assert!(!wide.overlaps(&apart));
+        assert_eq!(span(5, 7).intersection(&span(7, 9)), Some(span(7, 7)));
+        assert_eq!(wide.intersection(&apart), None);
+        assert_eq!(span(2, 4).union(&span(5, 6)), Some(span(2, 6)));
+        assert_eq!(wide.union(&apart), None);
+        assert_eq!(wide.len(), 7);
+        assert_eq!(span(0, u32::MAX).len(), 1 << 32);
+
+        assert_eq!("6-6".parse::<Interval>().unwrap(), span(6, 6));
+        assert!("6-4".parse::<Interval>().is_err());
+        assert!("6".parse::<Interval>().is_err());
+    }
+
+    #[test]
+    fn interval_set_test() {
+        let set: IntervalSet = [
+            span(10, 12),
+            span(1, 3),
+            span(5, 6),
+            span(4, 4),
+            span(20, 25),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.intervals(), &[span(1, 6), span(10, 12), span(20, 25)]);
+        assert_eq!(set.len(), 15);
+        assert_eq!(set.gaps(), vec![span(7, 9), span(13, 19)]);
+        assert!(set.contains(11));
+        assert!(!set.contains(13));
+
+        // One interval covering several merges them all.
+        let mut set = set;
+        set.insert(span(5, 21));
+        assert_eq!(set.intervals(), &[span(1, 25)]);
+        assert!(set.gaps().is_empty());
+    }
+}
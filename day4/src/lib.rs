@@ -0,0 +1,214 @@
+/// Checks the elves section cleaning assignment list to determine the number of overlapping
+/// section cleaning groups.
+/// Notes:
+///   - Each input line represents a cleaning assignment group "a-b,x-y,..." where the first elf
+///     is assigned sections a-b, the second elf sections x-y and so on. The puzzle only has pairs.
+///   - Counts for both complete (part 1) and partial (part 2) overlapping are required.
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use common::Solution;
+
+pub mod interval;
+
+use crate::interval::*;
+
+// The number of assignment groups that overlap.
+#[derive(Debug, Default, PartialEq)]
+pub struct Overlaps {
+    pub full: u32,
+    pub partial: u32,
+}
+
+// Parse the cleaning assignment groups, one group of comma separated spans per line.
+pub fn parse(data: &str) -> Result<Vec<Vec<Interval>>, Box<dyn Error>> {
+    let mut groups = vec![];
+
+    for (number, line) in data.lines().enumerate() {
+        let group = line
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<Interval>, _>>()
+            .map_err(|err| format!("line {}: {} in {:?}", number + 1, err, line))?;
+
+        groups.push(group);
+    }
+
+    Ok(groups)
+}
+
+// The assignment statistics of a single group.
+#[derive(Debug, Default, PartialEq)]
+pub struct GroupStats {
+    // Whether any elf's assignment is completely contained by another elf's.
+    pub contained: bool,
+    // The number of pairs of elves whose assignments share any section.
+    pub overlaps: usize,
+    // The number of distinct sections assigned to the group.
+    pub sections: u64,
+}
+
+// Compare every pair of assignments in the group.
+pub fn group_stats(group: &[Interval]) -> GroupStats {
+    let mut stats = GroupStats {
+        sections: group.iter().copied().collect::<IntervalSet>().len(),
+        ..GroupStats::default()
+    };
+
+    for (index, first) in group.iter().enumerate() {
+        for second in &group[index + 1..] {
+            stats.contained |= first.contains(second) || second.contains(first);
+            stats.overlaps += first.overlaps(second) as usize;
+        }
+    }
+
+    stats
+}
+
+// Count the cleaning assignment groups that overlap.
+pub fn overlaps(groups: &[Vec<Interval>]) -> Overlaps {
+    let mut overlaps = Overlaps::default();
+
+    for group in groups {
+        let stats = group_stats(group);
+        overlaps.full += stats.contained as u32;
+        overlaps.partial += (stats.overlaps > 0) as u32;
+    }
+
+    overlaps
+}
+
+// The sections covered by at least one elf.
+pub fn covered(groups: &[Vec<Interval>]) -> IntervalSet {
+    groups.iter().flatten().copied().collect()
+}
+
+// The sections assigned to every elf, or None if no section is.
+pub fn shared(groups: &[Vec<Interval>]) -> Option<Interval> {
+    let mut intervals = groups.iter().flatten();
+    let first = *intervals.next()?;
+
+    intervals.try_fold(first, |shared, interval| shared.intersection(interval))
+}
+
+// The number of elves assigned to each section from the first to the last assigned section,
+// as runs of neighboring sections with the same count.
+// Example:
+//   2-4,3-5 => [(2-2, 1), (3-4, 2), (5-5, 1)]
+pub fn coverage(groups: &[Vec<Interval>]) -> Vec<(Interval, u32)> {
+    // The change in the count at the start of each run, ends are stored one past the interval
+    // since they are inclusive.
+    let mut changes = BTreeMap::<u64, i64>::new();
+    for interval in groups.iter().flatten() {
+        *changes.entry(interval.start as u64).or_insert(0) += 1;
+        *changes.entry(interval.end as u64 + 1).or_insert(0) -= 1;
+    }
+
+    let mut runs: Vec<(Interval, u32)> = vec![];
+    let mut count = 0;
+    let mut changes = changes.into_iter().peekable();
+
+    while let Some((start, change)) = changes.next() {
+        count += change;
+
+        if let Some((next, _)) = changes.peek() {
+            let run = Interval::new(start as u32, (next - 1) as u32).unwrap();
+
+            // An interval ending right where another starts doesn't change the count, so the
+            // runs on either side are joined.
+            match runs.last_mut() {
+                Some((last, last_count)) if *last_count == count as u32 => last.end = run.end,
+                _ => runs.push((run, count as u32)),
+            }
+        }
+    }
+
+    runs
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Vec<Interval>>;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|group| group_stats(group).contained)
+            .count()
+            .to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(input
+            .iter()
+            .filter(|group| group_stats(group).overlaps > 0)
+            .count()
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn group_stats_test() {
+        let groups = parse("2-8,3-7,10-12\n1-3,2-4,3-5,6-6\n1-1").unwrap();
+
+        assert_eq!(
+            group_stats(&groups[0]),
+            GroupStats {
+                contained: true,
+                overlaps: 1,
+                sections: 10,
+            }
+        );
+        assert_eq!(
+            group_stats(&groups[1]),
+            GroupStats {
+                contained: false,
+                overlaps: 3,
+                sections: 6,
+            }
+        );
+        assert_eq!(
+            group_stats(&groups[2]),
+            GroupStats {
+                sections: 1,
+                ..GroupStats::default()
+            }
+        );
+        assert!(parse("1-2,,3-4").is_err());
+    }
+
+    #[test]
+    fn coverage_test() {
+        let groups = parse("2-4,3-5\n8-8").unwrap();
+
+        assert_eq!(
+            coverage(&groups),
+            vec![
+                (span(2, 2), 1),
+                (span(3, 4), 2),
+                (span(5, 5), 1),
+                (span(6, 7), 0),
+                (span(8, 8), 1),
+            ]
+        );
+        assert_eq!(shared(&groups), None);
+        assert_eq!(shared(&groups[..1]), Some(span(3, 4)));
+
+        // Neighboring assignments make a single run.
+        let groups = parse("1-2,3-4\n5-6,5-6").unwrap();
+        assert_eq!(coverage(&groups), vec![(span(1, 4), 1), (span(5, 6), 2)]);
+    }
+}
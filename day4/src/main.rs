@@ -1,82 +1,84 @@
-/// Checks the elves section cleaning assignment list to determine the number of overlapping
-/// section cleaning pairs.
-/// Notes:
-///   - Each input line represents a cleaning assignment pair "a-b,x-y" where the first elf
-///     is assigned sections a-b and the second elf sections x-y.
-///   - Counts for both complete (part 1) and partial (part 2) overlapping are required.
 use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs;
 
-// Process the input file to determine the number of cleaning assignment pairs that overlap.
-fn find_overlap(path: String) -> Result<(), Box<dyn Error>> {
-    let mut full_overlap = 0;
-    let mut partial_overlap = 0;
+use day4::*;
 
-    for raw in BufReader::new(File::open(path)?).lines() {
-        let line = raw?;
-
-        let spans: Vec<&str> = line.split(",").collect();
-        if spans.len() != 2 {
-            panic!("invalid input line: {}", line);
-        }
-
-        let first: Vec<&str> = spans[0].split("-").collect();
-        if spans.len() != 2 {
-            panic!("invalid first span in line: {}", line);
-        }
-
-        let second: Vec<&str> = spans[1].split("-").collect();
-        if spans.len() != 2 {
-            panic!("invalid second span in line: {}", line);
-        }
-
-        let inita: u32 = first[0].parse()?;
-        let enda: u32 = first[1].parse()?;
-        let initb: u32 = second[0].parse()?;
-        let endb: u32 = second[1].parse()?;
+// Counts the overlapping assignment groups, with optional reports:
+//   day4 <input file> [--groups] [--histogram]
+//   --groups     the containment, overlapping pairs and distinct sections of every group
+//   --histogram  the number of elves assigned to each section
+fn main() {
+    let mut path = None;
+    let mut groups_report = false;
+    let mut histogram = false;
 
-        if inita < initb {
-            if enda >= initb {
-                partial_overlap += 1;
-            }
-            if enda >= endb {
-                full_overlap += 1;
-            }
-        } else if initb < inita {
-            if endb >= inita {
-                partial_overlap += 1;
-            }
-            if endb >= enda {
-                full_overlap += 1;
-            }
-        } else {
-            full_overlap += 1;
-            partial_overlap += 1;
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--groups" => groups_report = true,
+            "--histogram" => histogram = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => panic!(
+                "expected the input file and optional --groups or --histogram, not {:?}",
+                arg
+            ),
         }
     }
 
+    let path = path.expect("expected the input file as an argument");
+    let data = fs::read_to_string(path).expect("couldn't read input file");
+    let groups = parse(&data).expect("failed to process input");
+    let overlaps = overlaps(&groups);
+
     println!(
-        "{} assignment pairs completely contain the other",
-        full_overlap
+        "{} assignment groups completely contain another",
+        overlaps.full
     );
     println!(
-        "{} assignment pairs partially contain the other",
-        partial_overlap
+        "{} assignment groups partially contain another",
+        overlaps.partial
     );
 
-    Ok(())
-}
+    let covered = covered(&groups);
+    let gaps: Vec<String> = covered.gaps().iter().map(|gap| gap.to_string()).collect();
+    println!(
+        "{} sections are covered, sections covered by no one: {}",
+        covered.len(),
+        if gaps.is_empty() {
+            "none".to_string()
+        } else {
+            gaps.join(", ")
+        }
+    );
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!(
-            "expected only the input file as an argument, not {:?}",
-            &args[1..]
-        );
+    match shared(&groups) {
+        Some(interval) => println!("sections covered by everyone: {}", interval),
+        None => println!("sections covered by everyone: none"),
     }
 
-    find_overlap(args[1].to_owned()).expect("failed to process input");
+    if groups_report {
+        println!("groups:");
+        for (number, group) in groups.iter().enumerate() {
+            let stats = group_stats(group);
+            println!(
+                "\tline {}: {} elves, {}contained, {} overlapping pairs, {} sections",
+                number + 1,
+                group.len(),
+                if stats.contained { "" } else { "not " },
+                stats.overlaps,
+                stats.sections
+            );
+        }
+    }
+
+    if histogram {
+        println!("coverage:");
+        for (interval, count) in coverage(&groups) {
+            println!(
+                "\t{:>11} {:>4} {}",
+                interval.to_string(),
+                count,
+                "#".repeat(count as usize)
+            );
+        }
+    }
 }
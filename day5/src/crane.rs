@@ -0,0 +1,168 @@
+use crate::{check_move, CargoError, Move};
+
+// A crane model, which decides how the boxes of a move are carried between stacks.
+pub trait Crane {
+    // The name of the crane, used in reports.
+    fn name(&self) -> String;
+
+    // Carry the boxes of the move, the stacks are numbered from 1 in the move. The cargo is left
+    // unchanged if a stack doesn't exist or doesn't have the boxes to move.
+    fn apply(&self, cargo: &mut [Vec<char>], step: &Move) -> Result<(), CargoError>;
+
+    // Carry the boxes of the move back, so the stacks are as they were before apply.
+    fn undo(&self, cargo: &mut [Vec<char>], step: &Move);
+}
+
+// Carry the boxes of a move in lifts of up to size boxes, each lift keeping its boxes in order.
+fn lift(cargo: &mut [Vec<char>], step: &Move, size: usize) -> Result<(), CargoError> {
+    let heights: Vec<usize> = cargo.iter().map(Vec::len).collect();
+    check_move(step, &heights)?;
+
+    let mut remaining = step.count;
+
+    while remaining > 0 {
+        let count = remaining.min(size);
+        let len = cargo[step.from - 1].len();
+        let mut transit = cargo[step.from - 1].split_off(len - count);
+        cargo[step.to - 1].append(&mut transit);
+
+        remaining -= count;
+    }
+
+    Ok(())
+}
+
+// Carry the boxes of a move lifted with lift back, undoing the last lift first.
+fn unlift(cargo: &mut [Vec<char>], step: &Move, size: usize) {
+    // Nothing was lifted, and the 9001's lift size is the count.
+    if step.count == 0 {
+        return;
+    }
+
+    // Every lift but the last is full, the last one carries what's left.
+    let mut count = match step.count % size {
+        0 => size,
+        rest => rest,
+    };
+    let mut remaining = step.count;
+
+    while remaining > 0 {
+        let len = cargo[step.to - 1].len();
+        let mut transit = cargo[step.to - 1].split_off(len - count);
+        cargo[step.from - 1].append(&mut transit);
+
+        remaining -= count;
+        count = size;
+    }
+}
+
+// Moves the boxes one at a time, which is copying in reverse order.
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn apply(&self, cargo: &mut [Vec<char>], step: &Move) -> Result<(), CargoError> {
+        lift(cargo, step, 1)
+    }
+
+    fn undo(&self, cargo: &mut [Vec<char>], step: &Move) {
+        unlift(cargo, step, 1);
+    }
+}
+
+// Moves the boxes all at once, which keeps them in order.
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn apply(&self, cargo: &mut [Vec<char>], step: &Move) -> Result<(), CargoError> {
+        lift(cargo, step, step.count)
+    }
+
+    fn undo(&self, cargo: &mut [Vec<char>], step: &Move) {
+        unlift(cargo, step, step.count);
+    }
+}
+
+// Moves up to capacity boxes at a time, a capacity of 1 is the same as the CrateMover 9000.
+pub struct Limited {
+    pub capacity: usize,
+}
+
+impl Crane for Limited {
+    fn name(&self) -> String {
+        format!("limited crane ({} boxes)", self.capacity)
+    }
+
+    fn apply(&self, cargo: &mut [Vec<char>], step: &Move) -> Result<(), CargoError> {
+        lift(cargo, step, self.capacity.max(1))
+    }
+
+    fn undo(&self, cargo: &mut [Vec<char>], step: &Move) {
+        unlift(cargo, step, self.capacity.max(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moved(crane: &dyn Crane) -> Vec<char> {
+        let mut cargo = vec![vec!['A', 'B', 'C', 'D', 'E'], vec![]];
+        crane
+            .apply(
+                &mut cargo,
+                &Move {
+                    count: 5,
+                    from: 1,
+                    to: 2,
+                    line: 1,
+                },
+            )
+            .unwrap();
+
+        cargo[1].clone()
+    }
+
+    #[test]
+    fn crane_test() {
+        assert_eq!(moved(&CrateMover9000), vec!['E', 'D', 'C', 'B', 'A']);
+        assert_eq!(moved(&CrateMover9001), vec!['A', 'B', 'C', 'D', 'E']);
+        // The top two boxes are lifted first, then the next two, then the last one.
+        assert_eq!(
+            moved(&Limited { capacity: 2 }),
+            vec!['D', 'E', 'B', 'C', 'A']
+        );
+        assert_eq!(moved(&Limited { capacity: 1 }), moved(&CrateMover9000));
+    }
+
+    #[test]
+    fn undo_test() {
+        let start = vec![vec!['A', 'B', 'C', 'D', 'E'], vec!['F']];
+        let step = Move {
+            count: 5,
+            from: 1,
+            to: 2,
+            line: 1,
+        };
+
+        let cranes: [&dyn Crane; 4] = [
+            &CrateMover9000,
+            &CrateMover9001,
+            &Limited { capacity: 2 },
+            &Limited { capacity: 5 },
+        ];
+        for crane in cranes {
+            let mut cargo = start.clone();
+            crane.apply(&mut cargo, &step).unwrap();
+            crane.undo(&mut cargo, &step);
+            assert_eq!(cargo, start, "{}", crane.name());
+        }
+    }
+}
@@ -0,0 +1,451 @@
+/// Simulates the unloading of cargo boxes in stacks. There are two different cargo movers, the
+/// cargo 9000 and the cargo 9001. The first one can only move one box at a time, while the second
+/// one is capable of moving multiple boxes from the same stack at once. Other crane models can be
+/// simulated by implementing the Crane trait.
+use std::error::Error;
+use std::fmt;
+
+use common::Solution;
+
+pub mod crane;
+pub mod render;
+pub mod reverse;
+
+use crate::crane::*;
+
+// A single crane instruction, moving count boxes from one stack to another, and the input line
+// it came from.
+// Note: the stacks are numbered from 1 in the input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+    pub line: usize,
+}
+
+// The starting cargo stacks and the moves made by the crane.
+#[derive(Debug, PartialEq)]
+pub struct Manifest {
+    pub cargo: Vec<Vec<char>>,
+    pub moves: Vec<Move>,
+}
+
+// What is wrong with an input line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    // The cargo drawing doesn't line up with the four character columns, at this column.
+    Misaligned(usize),
+    // The line isn't a "move N from A to B" instruction.
+    Malformed(String),
+    // The move uses a stack that doesn't exist.
+    StackOutOfRange {
+        stack: usize,
+        stacks: usize,
+    },
+    // The move takes more boxes than the stack has when the move is made.
+    OverDraw {
+        stack: usize,
+        count: usize,
+        available: usize,
+    },
+}
+
+// A problem found on an input line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CargoError {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for CargoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+
+        match &self.problem {
+            Problem::Misaligned(column) => {
+                write!(f, "the cargo is misaligned at column {}", column)
+            }
+            Problem::Malformed(line) => {
+                write!(f, "expected \"move N from A to B\", not {:?}", line)
+            }
+            Problem::StackOutOfRange { stack, stacks } => {
+                write!(
+                    f,
+                    "stack {} doesn't exist, there are {} stacks",
+                    stack, stacks
+                )
+            }
+            Problem::OverDraw {
+                stack,
+                count,
+                available,
+            } => write!(
+                f,
+                "can't move {} boxes from stack {} which only has {}",
+                count, stack, available
+            ),
+        }
+    }
+}
+
+impl Error for CargoError {}
+
+// Parse the cargo and the moves from the input, failing on the first problem.
+pub fn parse(data: &str) -> Result<Manifest, CargoError> {
+    parse_with(data, Checks::Strict).map(|(manifest, _)| manifest)
+}
+
+// Parse the cargo and the moves from the input, skipping the moves that are malformed or can't be
+// made and returning their problems with the manifest. Problems with the cargo drawing still
+// fail.
+pub fn parse_lenient(data: &str) -> Result<(Manifest, Vec<CargoError>), CargoError> {
+    parse_with(data, Checks::Lenient)
+}
+
+// Parse the cargo and the moves from the input without checking the moves can be made from the
+// drawn cargo, for when the drawing is the thing in doubt.
+pub fn parse_unchecked(data: &str) -> Result<Manifest, CargoError> {
+    parse_with(data, Checks::Unchecked).map(|(manifest, _)| manifest)
+}
+
+// How the moves are checked against the drawn cargo while parsing.
+#[derive(Clone, Copy, PartialEq)]
+enum Checks {
+    // Fail on the first move that can't be made.
+    Strict,
+    // Skip the moves that can't be made.
+    Lenient,
+    // Only check the moves are well formed.
+    Unchecked,
+}
+
+fn parse_with(data: &str, checks: Checks) -> Result<(Manifest, Vec<CargoError>), CargoError> {
+    let mut lines = data.lines().enumerate();
+    let cargo = parse_cargo(&mut lines.by_ref().map(|(_, line)| line))?;
+
+    let mut moves = vec![];
+    let mut skipped = vec![];
+    let mut heights: Vec<usize> = cargo.iter().map(Vec::len).collect();
+
+    for (index, line) in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = parse_move(line, index + 1).and_then(|step| {
+            if checks != Checks::Unchecked {
+                check_move(&step, &heights)?;
+                heights[step.from - 1] -= step.count;
+                heights[step.to - 1] += step.count;
+            }
+            Ok(step)
+        });
+
+        match result {
+            Ok(step) => moves.push(step),
+            Err(error) if checks == Checks::Lenient => skipped.push(error),
+            Err(error) => return Err(error),
+        }
+    }
+
+    Ok((Manifest { cargo, moves }, skipped))
+}
+
+// Parse the first part of the input file to build the cargo array.
+// Note: The layout of the cargo input is:
+//       [G]
+//       [E]     [F]
+//   [A] [B] [C] [D]
+//    1   2   3   4
+//
+//   Where each cargo stack is represented by 4 characters, a '[', then the cargo identifier,
+//   then a ']', and finally a space. The last row of the cargo input file is the list of stack
+//   numbers, but this can be determined by dividing the index by 4 as well. The widest line
+//   decides the number of stacks, so trailing spaces can be left out, even on the footer.
+pub fn parse_cargo<'a>(
+    input: &mut impl Iterator<Item = &'a str>,
+) -> Result<Vec<Vec<char>>, CargoError> {
+    let mut cargo = vec![];
+
+    for (index, line) in input.enumerate() {
+        let tokens: Vec<char> = line.chars().collect();
+
+        // A blank line in the input separates the cargo from the moves.
+        if line.trim().is_empty() {
+            break;
+        }
+
+        // The footer only numbers the stacks, and its last number sits one column before the end
+        // of the stack rather than two.
+        if line
+            .trim_start()
+            .starts_with(|token: char| token.is_ascii_digit())
+        {
+            if cargo.len() < (tokens.len() + 2) / 4 {
+                cargo.resize((tokens.len() + 2) / 4, vec![]);
+            }
+            continue;
+        }
+
+        if cargo.len() < (tokens.len() + 1) / 4 {
+            cargo.resize((tokens.len() + 1) / 4, vec![]);
+        }
+
+        for start in (0..tokens.len()).step_by(4) {
+            let misaligned = |column: usize| CargoError {
+                line: index + 1,
+                problem: Problem::Misaligned(column + 1),
+            };
+
+            match tokens[start..tokens.len().min(start + 3)] {
+                ['[', unit, ']'] => cargo[start / 4].push(unit),
+                [' ', ' ', ' '] => {}
+                _ => return Err(misaligned(start)),
+            }
+
+            if tokens
+                .get(start + 3)
+                .is_some_and(|separator| *separator != ' ')
+            {
+                return Err(misaligned(start + 3));
+            }
+        }
+    }
+
+    for stack in cargo.iter_mut() {
+        stack.reverse();
+    }
+
+    Ok(cargo)
+}
+
+// Parse a "move N from A to B" instruction.
+fn parse_move(line: &str, number: usize) -> Result<Move, CargoError> {
+    let malformed = || CargoError {
+        line: number,
+        problem: Problem::Malformed(line.to_string()),
+    };
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    match tokens.as_slice() {
+        ["move", count, "from", from, "to", to] => Ok(Move {
+            count: count.parse().map_err(|_| malformed())?,
+            from: from.parse().map_err(|_| malformed())?,
+            to: to.parse().map_err(|_| malformed())?,
+            line: number,
+        }),
+        _ => Err(malformed()),
+    }
+}
+
+// Check that the move's stacks exist and that the stack it takes from has enough boxes, given the
+// height of every stack before the move.
+pub(crate) fn check_move(step: &Move, heights: &[usize]) -> Result<(), CargoError> {
+    let fail = |problem| {
+        Err(CargoError {
+            line: step.line,
+            problem,
+        })
+    };
+
+    for stack in [step.from, step.to] {
+        if stack == 0 || stack > heights.len() {
+            return fail(Problem::StackOutOfRange {
+                stack,
+                stacks: heights.len(),
+            });
+        }
+    }
+
+    if step.count > heights[step.from - 1] {
+        return fail(Problem::OverDraw {
+            stack: step.from,
+            count: step.count,
+            available: heights[step.from - 1],
+        });
+    }
+
+    Ok(())
+}
+
+// The box on top of each stack, empty stacks are skipped.
+pub fn tops(cargo: &[Vec<char>]) -> String {
+    cargo.iter().filter_map(|stack| stack.last()).collect()
+}
+
+// The moves made by a single crane.
+#[derive(Debug, PartialEq)]
+pub struct Simulation {
+    pub crane: String,
+    // The stacks after each move, in move order.
+    pub history: Vec<Vec<Vec<char>>>,
+    // The top boxes after the last move.
+    pub tops: String,
+}
+
+// Run the moves with every crane, each starting from its own copy of the cargo, failing on the
+// first move that can't be made.
+pub fn simulate(manifest: &Manifest, cranes: &[&dyn Crane]) -> Result<Vec<Simulation>, CargoError> {
+    cranes
+        .iter()
+        .map(|crane| {
+            let mut cargo = manifest.cargo.clone();
+            let mut history = Vec::with_capacity(manifest.moves.len());
+
+            for step in &manifest.moves {
+                crane.apply(&mut cargo, step)?;
+                history.push(cargo.clone());
+            }
+
+            Ok(Simulation {
+                crane: crane.name(),
+                history,
+                tops: tops(&cargo),
+            })
+        })
+        .collect()
+}
+
+// The top boxes after running every move with the crane.
+pub fn final_tops(manifest: &Manifest, crane: &dyn Crane) -> Result<String, CargoError> {
+    let mut cargo = manifest.cargo.clone();
+    for step in &manifest.moves {
+        crane.apply(&mut cargo, step)?;
+    }
+
+    Ok(tops(&cargo))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Manifest;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(data)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(final_tops(input, &CrateMover9000)?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        Ok(final_tops(input, &CrateMover9001)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO: &str = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\n";
+
+    fn problems(data: &str) -> Vec<(usize, Problem)> {
+        let (_, skipped) = parse_lenient(data).unwrap();
+        skipped
+            .into_iter()
+            .map(|error| (error.line, error.problem))
+            .collect()
+    }
+
+    #[test]
+    fn move_errors_test() {
+        let moves = "move 1 from 2 to 1\n\
+            move 2 from 4 to 1\n\
+            shift 1 from 1 to 2\n\
+            move 1 from 1\n\
+            move 4 from 1 to 3\n\
+            move 3 from 1 to 3\n";
+        let data = format!("{}{}", CARGO, moves);
+
+        assert_eq!(
+            problems(&data),
+            vec![
+                (
+                    7,
+                    Problem::StackOutOfRange {
+                        stack: 4,
+                        stacks: 3
+                    }
+                ),
+                (8, Problem::Malformed("shift 1 from 1 to 2".to_string())),
+                (9, Problem::Malformed("move 1 from 1".to_string())),
+                (
+                    10,
+                    Problem::OverDraw {
+                        stack: 1,
+                        count: 4,
+                        available: 3,
+                    }
+                ),
+            ]
+        );
+
+        let (manifest, _) = parse_lenient(&data).unwrap();
+        assert_eq!(manifest.moves.len(), 2);
+        assert_eq!(manifest.moves[1].line, 11);
+
+        let error = parse(&data).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 7: stack 4 doesn't exist, there are 3 stacks"
+        );
+    }
+
+    #[test]
+    fn unchecked_moves_test() {
+        // Unchecked moves are only checked as they're made, so a bad one is an error instead of
+        // a panic.
+        let data = format!("{}move 1 from 2 to 1\nmove 5 from 3 to 1\n", CARGO);
+        let manifest = parse_unchecked(&data).unwrap();
+        assert_eq!(manifest.moves.len(), 2);
+
+        let over_draw = CargoError {
+            line: 7,
+            problem: Problem::OverDraw {
+                stack: 3,
+                count: 5,
+                available: 1,
+            },
+        };
+        assert_eq!(
+            final_tops(&manifest, &CrateMover9001),
+            Err(over_draw.clone())
+        );
+        assert_eq!(simulate(&manifest, &[&CrateMover9000]), Err(over_draw));
+
+        let manifest = parse_unchecked(&format!("{}move 1 from 9 to 1\n", CARGO)).unwrap();
+        assert_eq!(
+            final_tops(&manifest, &CrateMover9000).unwrap_err().problem,
+            Problem::StackOutOfRange {
+                stack: 9,
+                stacks: 3
+            }
+        );
+    }
+
+    #[test]
+    fn cargo_errors_test() {
+        // The trailing spaces of each row can be left out.
+        let manifest = parse(CARGO).unwrap();
+        assert_eq!(
+            manifest.cargo,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+
+        // Only the footer reaches the last stack when it starts out empty.
+        let manifest = parse("[A]\n[B] [C]\n 1   2   3\n\nmove 1 from 1 to 3\n").unwrap();
+        assert_eq!(manifest.cargo, vec![vec!['B', 'A'], vec!['C'], vec![]]);
+        assert_eq!(manifest.moves.len(), 1);
+
+        let error = parse("[A]  [B]\n 1   2\n").unwrap_err();
+        assert_eq!(
+            error,
+            CargoError {
+                line: 1,
+                problem: Problem::Misaligned(5),
+            }
+        );
+    }
+}
@@ -1,129 +1,158 @@
-/// Simulates the unloading of cargo boxes in stacks. There are two different cargo movers, the
-/// cargo 9000 and the cargo 9001. The first one can only move one box at a time, while the second
-/// one is capable of moving multiple boxes from the same stack at once.
 use std::env;
-use std::error::Error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-// Process the input file and simulate the cargo movement for both cargo movers.
-fn simulate(path: String) -> Result<(), Box<dyn Error>> {
-    let mut input = BufReader::new(File::open(path)?);
-    let mut cargo_9000 = parse_cargo(&mut input)?;
-
-    let mut cargo_9001 = vec![];
-    for stack in cargo_9000.iter() {
-        let mut temp = vec![];
-
-        for unit in stack {
-            temp.push(*unit);
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use day5::crane::*;
+use day5::render::*;
+use day5::reverse::*;
+use day5::*;
+
+// Simulates the cargo moves with the cargo 9000 and 9001, and optionally extra crane models:
+//   day5 <input file> [--capacity K]... [--history] [--render [--every N] [--animate MS]] [--dump]
+//     [--lenient]
+//   --capacity  adds a crane that moves up to K boxes at a time
+//   --history   prints the top boxes after every move
+//   --render    draws the stacks after every move, or every Nth move
+//   --animate   redraws the stacks in place, waiting MS milliseconds between moves
+//   --dump      draws the final stacks in the input's layout
+//   --lenient   skips the moves that are malformed or can't be made, and reports them
+// or reconstructs the starting stacks from final stacks drawn like --dump, running the moves
+// backwards for each crane, and compares them to the input's drawing:
+//   day5 <input file> --reverse <final stacks file> [--capacity K]...
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut path = None;
+    let mut capacities = vec![];
+    let mut history = false;
+    let mut render = false;
+    let mut every = 1;
+    let mut delay = None;
+    let mut dump = false;
+    let mut lenient = false;
+    let mut reverse = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--capacity" => {
+                let capacity = args.next().expect("--capacity needs a number of boxes");
+                capacities.push(Limited {
+                    capacity: capacity.parse().expect("invalid capacity"),
+                });
+            }
+            "--history" => history = true,
+            "--render" => render = true,
+            "--every" => {
+                let count = args.next().expect("--every needs a number of moves");
+                every = count.parse().expect("invalid number of moves");
+            }
+            "--animate" => {
+                let delay_ms = args
+                    .next()
+                    .expect("--animate needs a delay in milliseconds");
+                delay = Some(Duration::from_millis(
+                    delay_ms.parse().expect("invalid delay"),
+                ));
+                render = true;
+            }
+            "--dump" => dump = true,
+            "--lenient" => lenient = true,
+            "--reverse" => {
+                reverse = Some(args.next().expect("--reverse needs the final stacks path"));
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => panic!("expected the input file path, not {:?}", arg),
         }
-        cargo_9001.push(temp);
     }
 
-    let (top_9000, top_9001) = simulate_cargo(input, &mut cargo_9000, &mut cargo_9001)?;
+    let path = path.expect("expected the input file path");
+    let data = fs::read_to_string(path).expect("couldn't read input file");
 
-    println!(
-        "the top cargo boxes for a cargo 9000 are {}",
-        top_9000.iter().collect::<String>()
-    );
-    println!(
-        "the top cargo boxes for a cargo 9001 are {}",
-        top_9001.iter().collect::<String>()
-    );
+    let mut cranes: Vec<&dyn Crane> = vec![&CrateMover9000, &CrateMover9001];
+    cranes.extend(capacities.iter().map(|crane| crane as &dyn Crane));
 
-    Ok(())
-}
+    if let Some(reverse) = reverse {
+        // The drawing may be the thing that's wrong, so the moves aren't checked against it.
+        let manifest = parse_unchecked(&data).unwrap_or_else(|err| panic!("{}", err));
+        let stacks = fs::read_to_string(reverse).expect("couldn't read final stacks file");
+        let last = parse_cargo(&mut stacks.lines()).unwrap_or_else(|err| panic!("{}", err));
 
-// Parse the first part of the input file to build the cargo array.
-// Note: The layout of the cargo input is:
-//       [G]
-//       [E]     [F]
-//   [A] [B] [C] [D]
-//    1   2   3   4
-//
-//   Where each cargo stack is represented by 4 characters, a '[', then the cargo identifier,
-//   then a ']', and finally a space. The last row of the cargo input file is the list of stack
-//   numbers, but this can be determined by dividing the index by 4 as well.
-fn parse_cargo(input: &mut BufReader<File>) -> Result<Vec<Vec<char>>, Box<dyn Error>> {
-    let mut cargo = vec![];
+        let starts = match reconstruct_any(&last, &manifest.moves, &cranes) {
+            Reconstruction::Unique(start) => vec![(vec!["every crane".to_string()], start)],
+            Reconstruction::Ambiguous(starts) => {
+                println!("the start depends on the crane");
+                starts
+            }
+            Reconstruction::Impossible(failures) => {
+                for (crane, error) in failures {
+                    println!("a {} can't reach the final stacks: {}", crane, error);
+                }
+                return;
+            }
+        };
+
+        for (names, start) in starts {
+            let matches = if start == manifest.cargo {
+                "matches"
+            } else {
+                "doesn't match"
+            };
+            println!(
+                "the start for {} {} the input's drawing:\n{}",
+                names.join(", "),
+                matches,
+                draw(&start)
+            );
+        }
 
-    for raw in input.lines() {
-        let line = raw?;
-        let tokens: Vec<char> = line.chars().collect();
+        return;
+    }
 
-        // A blank line in the input separates the cargo from the moves.
-        if line.trim().len() == 0 {
-            break;
+    let manifest = if lenient {
+        let (manifest, skipped) = parse_lenient(&data).unwrap_or_else(|err| panic!("{}", err));
+        for error in skipped {
+            println!("skipped {}", error);
         }
-
-        if cargo.len() == 0 {
-            for _ in 0..(tokens.len() / 4) + 1 {
-                cargo.push(vec![]);
+        manifest
+    } else {
+        parse(&data).unwrap_or_else(|err| panic!("{}", err))
+    };
+
+    let simulations = simulate(&manifest, &cranes).unwrap_or_else(|err| panic!("{}", err));
+    for simulation in simulations {
+        println!(
+            "the top cargo boxes for a {} are {}",
+            simulation.crane, simulation.tops
+        );
+
+        if history {
+            for (step, cargo) in manifest.moves.iter().zip(&simulation.history) {
+                println!(
+                    "\tmove {} from {} to {}: {}",
+                    step.count,
+                    step.from,
+                    step.to,
+                    tops(cargo)
+                );
             }
         }
 
-        for i in (0..tokens.len()).step_by(4) {
-            if tokens[i] == '[' && tokens[i + 2] == ']' {
-                cargo[i / 4].push(tokens[i + 1]);
+        if render {
+            for frame in frames(&manifest, &simulation, every) {
+                match delay {
+                    Some(delay) => {
+                        // Clear the terminal and move the cursor home before each frame.
+                        print!("\x1b[2J\x1b[H{}\n{}", simulation.crane, frame);
+                        thread::sleep(delay);
+                    }
+                    None => println!("{}", frame),
+                }
             }
         }
-    }
 
-    for i in 0..cargo.len() {
-        cargo[i] = cargo[i].to_owned().into_iter().rev().collect();
-    }
-
-    Ok(cargo)
-}
-
-// Simulate the cargo moves to determine the top boxes on each final stack. The cargo 9001 can move
-// multiple boxes at a time and the cargo 9000 can only move one box at a time.
-fn simulate_cargo(
-    input: BufReader<File>,
-    cargo_9000: &mut Vec<Vec<char>>,
-    cargo_9001: &mut Vec<Vec<char>>,
-) -> Result<(Vec<char>, Vec<char>), Box<dyn Error>> {
-    for raw in input.lines() {
-        let line = raw?;
-        let tokens: Vec<&str> = line.split(' ').collect();
-
-        // Ensure that the line is a move instruction.
-        if tokens[0] != "move" || tokens[2] != "from" || tokens[4] != "to" {
-            panic!("invalid instruction line {}", line);
+        if dump {
+            let last = simulation.history.last().unwrap_or(&manifest.cargo);
+            print!("{}", draw(last));
         }
-
-        let count: usize = tokens[1].parse()?;
-        let from: usize = tokens[3].parse()?;
-        let to: usize = tokens[5].parse()?;
-
-        // Move the boxes one at a time, which is copying in reverse order.
-        let len = cargo_9000[from - 1].len();
-        let mut transit_9000: Vec<char> = cargo_9000[from - 1]
-            .split_off(len - count)
-            .iter()
-            .cloned()
-            .rev()
-            .collect();
-        cargo_9000[to - 1].append(&mut transit_9000);
-
-        // Move the boxes all at once.
-        let len = cargo_9001[from - 1].len();
-        let mut transit_9001 = cargo_9001[from - 1].split_off(len - count);
-        cargo_9001[to - 1].append(&mut transit_9001);
     }
-
-    let tops_9000: Vec<char> = cargo_9000.iter_mut().filter_map(|x| x.pop()).collect();
-    let tops_9001: Vec<char> = cargo_9001.iter_mut().filter_map(|x| x.pop()).collect();
-
-    Ok((tops_9000, tops_9001))
-}
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!("expected the input file path, not {:?}", &args[1..]);
-    }
-
-    simulate(args[1].to_owned()).expect("failed to simulate cargo");
 }
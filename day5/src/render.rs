@@ -0,0 +1,106 @@
+use std::fmt::Write;
+
+use crate::{Manifest, Simulation};
+
+// Draw the stacks in the input's layout, one row per height with a numbered footer, which
+// parse_cargo can read back.
+// Example:
+//       [D]
+//   [N] [C]
+//   [Z] [M] [P]
+//    1   2   3
+// Note: empty positions are drawn as spaces so every row is the full width, which parse_cargo
+//   relies on to count the stacks.
+pub fn draw(cargo: &[Vec<char>]) -> String {
+    let height = cargo.iter().map(Vec::len).max().unwrap_or(0);
+    let mut out = String::new();
+
+    for row in (0..height).rev() {
+        let cells: Vec<String> = cargo
+            .iter()
+            .map(|stack| match stack.get(row) {
+                Some(unit) => format!("[{}]", unit),
+                None => "   ".to_string(),
+            })
+            .collect();
+        writeln!(out, "{}", cells.join(" ")).unwrap();
+    }
+
+    let footer: Vec<String> = (1..=cargo.len())
+        .map(|number| format!("{:^3}", number))
+        .collect();
+    writeln!(out, "{}", footer.join(" ")).unwrap();
+
+    out
+}
+
+// Draw the starting stacks and then the stacks after every Nth move of the simulation, the last
+// move is always drawn. Each frame has a title line naming the move.
+pub fn frames(manifest: &Manifest, simulation: &Simulation, every: usize) -> Vec<String> {
+    let mut frames = vec![format!("start\n{}", draw(&manifest.cargo))];
+    let total = simulation.history.len();
+
+    for (index, (step, cargo)) in manifest.moves.iter().zip(&simulation.history).enumerate() {
+        let number = index + 1;
+        if number % every.max(1) != 0 && number != total {
+            continue;
+        }
+
+        frames.push(format!(
+            "move {} of {}: move {} from {} to {}\n{}",
+            number,
+            total,
+            step.count,
+            step.from,
+            step.to,
+            draw(cargo)
+        ));
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::*;
+    use crate::*;
+
+    const EXAMPLE: &str = "    [D]    \n\
+        [N] [C]    \n\
+        [Z] [M] [P]\n\
+        \x201   2   3 \n\
+        \n\
+        move 1 from 2 to 1\n\
+        move 3 from 1 to 3\n\
+        move 2 from 2 to 1\n\
+        move 1 from 1 to 2\n";
+
+    #[test]
+    fn draw_test() {
+        let manifest = parse(EXAMPLE).unwrap();
+        assert_eq!(
+            draw(&manifest.cargo),
+            EXAMPLE[..EXAMPLE.find("\n\n").unwrap() + 1]
+        );
+
+        // The final stacks read back to the same cargo.
+        let simulation = simulate(&manifest, &[&CrateMover9001]).unwrap().remove(0);
+        let last = simulation.history.last().unwrap();
+        let drawing = draw(last);
+        assert_eq!(&parse_cargo(&mut drawing.lines()).unwrap(), last);
+    }
+
+    #[test]
+    fn frames_test() {
+        let manifest = parse(EXAMPLE).unwrap();
+        let simulation = simulate(&manifest, &[&CrateMover9000]).unwrap().remove(0);
+
+        let frames = frames(&manifest, &simulation, 3);
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0].starts_with("start\n"));
+        assert!(frames[1].starts_with("move 3 of 4: move 2 from 2 to 1\n"));
+        assert!(frames[2].starts_with("move 4 of 4: move 1 from 1 to 2\n"));
+        assert!(frames[2].ends_with(" 1   2   3 \n"));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::crane::Crane;
+use crate::{check_move, CargoError, Move};
+
+// Run the moves backwards from the final stacks to find the stacks the crane started from.
+// Note: every crane model carries the boxes of a move in a fixed way, so undoing the moves gives
+//   the only possible start for that crane. The moves can't be undone when a stack they took from
+//   doesn't end up with the boxes to give back, which is reported as an over-draw on the move's
+//   line.
+pub fn reconstruct(
+    cargo: &[Vec<char>],
+    moves: &[Move],
+    crane: &dyn Crane,
+) -> Result<Vec<Vec<char>>, CargoError> {
+    let mut cargo = cargo.to_vec();
+
+    for step in moves.iter().rev() {
+        let heights: Vec<usize> = cargo.iter().map(Vec::len).collect();
+        let back = Move {
+            from: step.to,
+            to: step.from,
+            ..*step
+        };
+
+        check_move(&back, &heights)?;
+        crane.undo(&mut cargo, step);
+    }
+
+    Ok(cargo)
+}
+
+// The starting stacks that lead to the final stacks, when the crane model isn't known.
+#[derive(Debug, PartialEq)]
+pub enum Reconstruction {
+    // Every crane that can make the moves started from the same stacks.
+    Unique(Vec<Vec<char>>),
+    // The cranes started from different stacks, listed with the names of the cranes that start
+    // from them.
+    Ambiguous(Vec<(Vec<String>, Vec<Vec<char>>)>),
+    // No crane can make the moves, with the reason for each crane.
+    Impossible(Vec<(String, CargoError)>),
+}
+
+// Reconstruct the starting stacks for every crane, grouping the cranes that agree.
+pub fn reconstruct_any(
+    cargo: &[Vec<char>],
+    moves: &[Move],
+    cranes: &[&dyn Crane],
+) -> Reconstruction {
+    let mut starts: Vec<(Vec<String>, Vec<Vec<char>>)> = vec![];
+    let mut failures = vec![];
+
+    for crane in cranes {
+        match reconstruct(cargo, moves, *crane) {
+            Ok(start) => match starts.iter_mut().find(|(_, other)| *other == start) {
+                Some((names, _)) => names.push(crane.name()),
+                None => starts.push((vec![crane.name()], start)),
+            },
+            Err(error) => failures.push((crane.name(), error)),
+        }
+    }
+
+    match starts.len() {
+        0 => Reconstruction::Impossible(failures),
+        1 => Reconstruction::Unique(starts.remove(0).1),
+        _ => Reconstruction::Ambiguous(starts),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::*;
+    use crate::{parse, Problem};
+
+    const EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+        move 1 from 2 to 1\n\
+        move 3 from 1 to 3\n\
+        move 2 from 2 to 1\n\
+        move 1 from 1 to 2\n";
+
+    fn finish(cargo: &[Vec<char>], moves: &[Move], crane: &dyn Crane) -> Vec<Vec<char>> {
+        let mut cargo = cargo.to_vec();
+        for step in moves {
+            crane.apply(&mut cargo, step).unwrap();
+        }
+
+        cargo
+    }
+
+    #[test]
+    fn reconstruct_test() {
+        let manifest = parse(EXAMPLE).unwrap();
+
+        for crane in [&CrateMover9000 as &dyn Crane, &CrateMover9001] {
+            let end = finish(&manifest.cargo, &manifest.moves, crane);
+            assert_eq!(
+                reconstruct(&end, &manifest.moves, crane).unwrap(),
+                manifest.cargo
+            );
+        }
+
+        // The last move put a box on stack 2, so it can't be undone when stack 2 ends empty.
+        let error = reconstruct(
+            &[vec![], vec![], vec!['Z']],
+            &manifest.moves,
+            &CrateMover9000,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            CargoError {
+                line: 9,
+                problem: Problem::OverDraw {
+                    stack: 2,
+                    count: 1,
+                    available: 0,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn zero_count_test() {
+        let manifest = parse(&format!("{}move 0 from 1 to 2\n", EXAMPLE)).unwrap();
+        let cranes: [&dyn Crane; 3] = [&CrateMover9000, &CrateMover9001, &Limited { capacity: 2 }];
+
+        for crane in cranes {
+            let end = finish(&manifest.cargo, &manifest.moves, crane);
+            assert_eq!(
+                reconstruct(&end, &manifest.moves, crane).unwrap(),
+                manifest.cargo,
+                "{}",
+                crane.name()
+            );
+        }
+    }
+
+    #[test]
+    fn reconstruct_any_test() {
+        let manifest = parse(EXAMPLE).unwrap();
+        let cranes: [&dyn Crane; 2] = [&CrateMover9000, &CrateMover9001];
+        let end = finish(&manifest.cargo, &manifest.moves, &CrateMover9000);
+
+        // The multi box moves are carried differently, so each crane needs its own start.
+        match reconstruct_any(&end, &manifest.moves, &cranes) {
+            Reconstruction::Ambiguous(starts) => {
+                assert_eq!(starts.len(), 2);
+                assert_eq!(starts[0].1, manifest.cargo);
+            }
+            other => panic!("expected an ambiguous start, got {:?}", other),
+        }
+
+        // Single box moves are the same for every crane.
+        let single = &manifest.moves[..1];
+        let end = finish(&manifest.cargo, single, &CrateMover9000);
+        assert_eq!(
+            reconstruct_any(&end, single, &cranes),
+            Reconstruction::Unique(manifest.cargo.clone())
+        );
+
+        assert!(matches!(
+            reconstruct_any(&vec![vec![]; 3], &manifest.moves, &cranes),
+            Reconstruction::Impossible(failures) if failures.len() == 2
+        ));
+    }
+}
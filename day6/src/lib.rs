@@ -0,0 +1,285 @@
+/// Analyzes a signal input to find the start packet and the message packet. The start packet is
+/// the first four token frame that contains unique values. The message packet is the first
+/// fourteen token fram that contains unique values.
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::hash::Hash;
+use std::io::{self, BufReader, Read};
+use std::{iter, mem, str};
+
+use common::Solution;
+
+// The search for the first frame of distinct tokens, fed one token at a time.
+// Note: the last position of every token is kept, so the frame can start just after the last
+//   repeat of the newest token without rescanning it, which is O(n) for any frame length.
+struct Search<T> {
+    length: usize,
+    last_seen: HashMap<T, usize>,
+    start: usize,
+    index: usize,
+}
+
+impl<T: Eq + Hash> Search<T> {
+    fn new(length: usize) -> Self {
+        Search {
+            length,
+            last_seen: HashMap::new(),
+            start: 0,
+            index: 0,
+        }
+    }
+
+    // Add the next token, returning the start of the frame once it has length distinct tokens.
+    fn push(&mut self, token: T) -> Option<usize> {
+        if let Some(seen) = self.last_seen.insert(token, self.index) {
+            self.start = self.start.max(seen + 1);
+        }
+        self.index += 1;
+
+        (self.index - self.start >= self.length).then(|| self.index - self.length)
+    }
+}
+
+// Find the start of the first frame of the given length with no repeated tokens, or None if
+// there isn't one.
+pub fn find_unique_packet(tokens: &[char], length: usize) -> Option<usize> {
+    if length == 0 {
+        return Some(0);
+    }
+
+    let mut search = Search::new(length);
+    tokens.iter().find_map(|token| search.push(token))
+}
+
+// The first frame of distinct tokens on a signal line.
+#[derive(Debug, PartialEq)]
+pub struct Marker {
+    pub start: usize,
+    pub packet: String,
+}
+
+// The marker search of a single signal line, keeping the last frame of tokens to report.
+struct LineSearch {
+    length: usize,
+    search: Search<char>,
+    frame: VecDeque<char>,
+    marker: Option<Marker>,
+}
+
+impl LineSearch {
+    fn new(length: usize) -> Self {
+        LineSearch {
+            length,
+            search: Search::new(length),
+            frame: VecDeque::new(),
+            // An empty frame is found before any token, even on a blank line.
+            marker: (length == 0).then(|| Marker {
+                start: 0,
+                packet: String::new(),
+            }),
+        }
+    }
+
+    fn push(&mut self, token: char) {
+        if self.marker.is_some() {
+            return;
+        }
+
+        self.frame.push_back(token);
+        if self.frame.len() > self.length {
+            self.frame.pop_front();
+        }
+
+        if let Some(start) = self.search.push(token) {
+            self.marker = Some(Marker {
+                start,
+                packet: self.frame.iter().collect(),
+            });
+        }
+    }
+}
+
+// Decode the UTF-8 characters of a stream one at a time.
+fn chars(signal: impl Read) -> impl Iterator<Item = io::Result<char>> {
+    let mut bytes = BufReader::new(signal).bytes();
+
+    iter::from_fn(move || {
+        let mut encoded = [0; 4];
+        encoded[0] = match bytes.next()? {
+            Ok(byte) => byte,
+            Err(err) => return Some(Err(err)),
+        };
+
+        // The leading byte gives the number of bytes in the character, anything else is invalid
+        // and left for from_utf8 to reject.
+        let width = match encoded[0] {
+            0x00..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 1,
+        };
+        for slot in &mut encoded[1..width] {
+            match bytes.next() {
+                Some(Ok(byte)) => *slot = byte,
+                Some(Err(err)) => return Some(Err(err)),
+                None => break,
+            }
+        }
+
+        let decoded = str::from_utf8(&encoded[..width]).ok();
+        Some(
+            decoded
+                .and_then(|token| token.chars().next())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8 signal")),
+        )
+    })
+}
+
+// Find the first frame of the given length with no repeated tokens on each line of the signal,
+// or None for the lines without one. The signal is read a character at a time, so it never has
+// to fit in memory.
+// Note: lines end like they do for str::lines, so the positions are character offsets that match
+//   find_unique_packet on the same line.
+pub fn find_markers(signal: impl Read, length: usize) -> io::Result<Vec<Option<Marker>>> {
+    let mut markers = vec![];
+    let mut line = LineSearch::new(length);
+    let mut empty = true;
+    let mut carriage_return = false;
+
+    for token in chars(signal) {
+        let token = token?;
+
+        if token == '\n' {
+            markers.push(mem::replace(&mut line, LineSearch::new(length)).marker);
+            empty = true;
+            carriage_return = false;
+            continue;
+        }
+
+        // A carriage return is only part of the line ending when a newline follows it.
+        if carriage_return {
+            line.push('\r');
+        }
+        carriage_return = token == '\r';
+        if !carriage_return {
+            line.push(token);
+        }
+        empty = false;
+    }
+
+    if carriage_return {
+        line.push('\r');
+    }
+    if !empty {
+        markers.push(line.marker);
+    }
+
+    Ok(markers)
+}
+
+// The position after the first unique packet of the given length on each signal line, joined
+// by commas.
+fn markers(signals: &[Vec<char>], length: usize) -> Result<String, Box<dyn Error>> {
+    let mut positions = vec![];
+    for (number, tokens) in signals.iter().enumerate() {
+        let start = find_unique_packet(tokens, length).ok_or_else(|| {
+            format!(
+                "line {}: no packet of {} distinct tokens",
+                number + 1,
+                length
+            )
+        })?;
+        positions.push((start + length).to_string());
+    }
+
+    Ok(positions.join(","))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(data: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(data.lines().map(|line| line.chars().collect()).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        markers(input, 4)
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Box<dyn Error>> {
+        markers(input, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(signal: &str) -> Vec<char> {
+        signal.chars().collect()
+    }
+
+    #[test]
+    fn find_unique_packet_test() {
+        let signal = tokens("mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(find_unique_packet(&signal, 4), Some(3));
+        assert_eq!(find_unique_packet(&signal, 14), Some(5));
+        assert_eq!(find_unique_packet(&signal, 1), Some(0));
+
+        // Too short, and too repetitive.
+        assert_eq!(find_unique_packet(&tokens("abc"), 4), None);
+        assert_eq!(find_unique_packet(&tokens("abcabcabc"), 4), None);
+    }
+
+    #[test]
+    fn find_markers_test() {
+        let signal = "bvwbjplbgvbhsrlpgdmjqwftvncz\r\naaaa\n\nnppdvjthqldpwncqszvftbrmjlhg";
+
+        assert_eq!(
+            find_markers(signal.as_bytes(), 4).unwrap(),
+            vec![
+                Some(Marker {
+                    start: 1,
+                    packet: "vwbj".to_string(),
+                }),
+                None,
+                None,
+                Some(Marker {
+                    start: 2,
+                    packet: "pdvj".to_string(),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn markers_match_test() {
+        // Both searches see the same tokens, so they find the same frames.
+        let signal = "ab\rcd\r\n\u{e9}\u{e9}abc\n\nab\r";
+        for length in [0, 3, 5] {
+            let lines: Vec<Option<usize>> = signal
+                .lines()
+                .map(|line| find_unique_packet(&tokens(line), length))
+                .collect();
+            let markers: Vec<Option<usize>> = find_markers(signal.as_bytes(), length)
+                .unwrap()
+                .into_iter()
+                .map(|marker| marker.map(|marker| marker.start))
+                .collect();
+
+            assert_eq!(markers, lines, "length {}", length);
+        }
+
+        assert_eq!(
+            find_markers("\u{e9}\u{e9}abc".as_bytes(), 3).unwrap(),
+            vec![Some(Marker {
+                start: 1,
+                packet: "\u{e9}ab".to_string(),
+            })]
+        );
+        assert!(find_markers(&b"ab\xffcd"[..], 2).is_err());
+    }
+}
@@ -1,81 +1,54 @@
-/// Analyzes a signal input to find the start packet and the message packet. The start packet is
-/// the first four token frame that contains unique values. The message packet is the first
-/// fourteen token fram that contains unique values.
-use std::collections::HashMap;
 use std::env;
-use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
 
-// Process the signal input and find the start and message packets.
-fn run(path: String) -> Result<(), Box<dyn Error>> {
-    for raw in BufReader::new(File::open(path)?).lines() {
-        let line = raw?;
-        let tokens: Vec<char> = line.chars().collect();
+use day6::find_markers;
 
-        let start_packet = find_unique_packet(&tokens, 4)?;
-        let message_packet = find_unique_packet(&tokens, 14)?;
-
-        println!(
-            "the start packet is {} and the signal starts at {}",
-            tokens[start_packet..start_packet + 4]
-                .iter()
-                .collect::<String>(),
-            start_packet + 4,
-        );
-
-        println!(
-            "the message packet is {} and the message starts at {}",
-            tokens[message_packet..message_packet + 14]
-                .iter()
-                .collect::<String>(),
-            message_packet + 14,
-        );
-    }
-
-    Ok(())
-}
-
-// Find the first unique packet of the given length.
-fn find_unique_packet(tokens: &Vec<char>, length: usize) -> Result<usize, Box<dyn Error>> {
-    let mut frame: HashMap<char, i32> = HashMap::new();
-    let mut start = 0;
-
-    'toploop: for index in 0..tokens.len() {
-        if index >= length {
-            let entry = frame.entry(tokens[start]).or_insert(0);
-            *entry -= 1;
-            if *entry <= 0 {
-                frame.remove(&tokens[start]);
+// Finds the start and message packets on each signal line, other packet lengths can be searched
+// for with the --length argument:
+//   day6 <input file> [--length N]...
+fn main() {
+    let mut args = env::args().skip(1);
+    let mut path = None;
+    let mut lengths = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--length" => {
+                let length = args.next().expect("--length needs a number of tokens");
+                lengths.push(length.parse().expect("invalid length"));
             }
-            start += 1;
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => panic!("expected the input file path, not {:?}", arg),
         }
+    }
 
-        let entry = frame.entry(tokens[index]).or_insert(0);
-        *entry += 1;
+    let path = path.expect("expected the input file path");
+    if lengths.is_empty() {
+        lengths = vec![4, 14];
+    }
 
-        if index >= length {
-            for count in frame.values() {
-                if *count != 1 {
-                    continue 'toploop;
-                }
+    for length in lengths {
+        let (packet, data) = match length {
+            4 => ("start packet".to_string(), "signal"),
+            14 => ("message packet".to_string(), "message"),
+            _ => (format!("{} token packet", length), "data"),
+        };
+
+        // The file is read again for each length so the signal never has to fit in memory.
+        let file = File::open(&path).expect("couldn't open input file");
+        let markers = find_markers(file, length).expect("failed to process the signal");
+
+        for (number, marker) in markers.into_iter().enumerate() {
+            match marker {
+                Some(marker) => println!(
+                    "the {} is {} and the {} starts at {}",
+                    packet,
+                    marker.packet,
+                    data,
+                    marker.start + length
+                ),
+                None => println!("there is no {} on line {}", packet, number + 1),
             }
-
-            break 'toploop;
         }
     }
-
-    Ok(start)
-}
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        panic!(
-            "expected only the path to the input file, not {:?}",
-            &args[1..]
-        );
-    }
-
-    run(args[1].to_owned()).expect("failed to process the signal");
 }